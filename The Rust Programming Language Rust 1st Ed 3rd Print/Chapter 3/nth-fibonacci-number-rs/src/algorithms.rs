@@ -0,0 +1,263 @@
+use num_bigint::BigUint;
+
+use crate::table;
+
+//Different ways of computing the same Fibonacci number, from the slowest to the fastest.
+//Note - n here is the index, and the complexities count big integer operations, which themselves
+//          get slower as the numbers grow (F(n) has about 0.694n bits).
+pub trait FibonacciAlgorithm {
+    fn name(&self) -> &'static str;
+
+    fn complexity(&self) -> &'static str;
+
+    //Largest index the algorithm can handle in a reasonable time (or without running out of
+    //stack), None when there is no such limit.
+    fn index_limit(&self) -> Option<usize>{
+        None
+    }
+
+    fn fibonacci(&self, index : usize) -> BigUint;
+}
+
+pub struct NaiveRecursive;
+pub struct Memoized;
+pub struct Iterative;
+pub struct MatrixExponentiation;
+pub struct FastDoubling;
+//Not one of the ALGORITHMS, it is what runs when no --algorithm is given.
+pub struct TableLookup;
+
+pub const ALGORITHMS : [&dyn FibonacciAlgorithm; 5] = [&NaiveRecursive, &Memoized, &Iterative,
+    &MatrixExponentiation, &FastDoubling];
+
+pub fn find_algorithm(name : &str) -> Option<&'static dyn FibonacciAlgorithm>{
+    ALGORITHMS.iter().copied().find(|algorithm| algorithm.name().eq_ignore_ascii_case(name.trim()))
+}
+
+pub fn algorithm_names() -> String{
+    ALGORITHMS.iter().map(|algorithm| algorithm.name()).collect::<Vec<_>>().join(", ")
+}
+
+pub fn check_index_limit(algorithm : &dyn FibonacciAlgorithm, index : usize) -> Result<(), String>{
+    match algorithm.index_limit() {
+        Some(limit) if index > limit => Err(format!("The {} algorithm only supports index up to \
+            {}, please choose a faster one with --algorithm ({})!", algorithm.name(), limit,
+            algorithm_names())),
+        _ => Ok(()),
+    }
+}
+
+//Straight from the definition F(n) = F(n - 1) + F(n - 2).
+//Note - Every call makes two more calls, and the same values get computed over and over again, so
+//          the number of calls grows like F(n) itself, about 1.618^n.
+impl FibonacciAlgorithm for NaiveRecursive {
+    fn name(&self) -> &'static str{
+        "naive"
+    }
+
+    fn complexity(&self) -> &'static str{
+        "O(2^n)"
+    }
+
+    fn index_limit(&self) -> Option<usize>{
+        Some(40)
+    }
+
+    fn fibonacci(&self, index : usize) -> BigUint{
+        if index < 2 {
+            return BigUint::from(index);
+        }
+
+        self.fibonacci(index - 1) + self.fibonacci(index - 2)
+    }
+}
+
+//Same recursion, but every value is only computed once and then remembered.
+//Note - The recursion still goes n calls deep, which is what limits the index here.
+impl Memoized {
+    fn fibonacci_memoized(index : usize, memo : &mut Vec<Option<BigUint>>) -> BigUint{
+        if let Some(value) = &memo[index] {
+            return value.clone();
+        }
+
+        let value = Memoized::fibonacci_memoized(index - 1, memo)
+            + Memoized::fibonacci_memoized(index - 2, memo);
+        memo[index] = Some(value.clone());
+        value
+    }
+}
+
+impl FibonacciAlgorithm for Memoized {
+    fn name(&self) -> &'static str{
+        "memoized"
+    }
+
+    fn complexity(&self) -> &'static str{
+        "O(n)"
+    }
+
+    fn index_limit(&self) -> Option<usize>{
+        Some(20_000)
+    }
+
+    fn fibonacci(&self, index : usize) -> BigUint{
+        let mut memo = vec![None; index.max(1) + 1];
+        memo[0] = Some(BigUint::from(0u32));
+        memo[1] = Some(BigUint::from(1u32));
+
+        Memoized::fibonacci_memoized(index, &mut memo)
+    }
+}
+
+//Walks up the sequence keeping only the last two values.
+impl FibonacciAlgorithm for Iterative {
+    fn name(&self) -> &'static str{
+        "iterative"
+    }
+
+    fn complexity(&self) -> &'static str{
+        "O(n)"
+    }
+
+    fn fibonacci(&self, index : usize) -> BigUint{
+        let mut current = BigUint::from(0u32);
+        let mut next = BigUint::from(1u32);
+
+        for _ in 0..index {
+            let sum = &current + &next;
+            current = next;
+            next = sum;
+        }
+
+        current
+    }
+}
+
+type Matrix = [[BigUint; 2]; 2];
+
+fn multiply(left : &Matrix, right : &Matrix) -> Matrix{
+    [[&left[0][0] * &right[0][0] + &left[0][1] * &right[1][0],
+      &left[0][0] * &right[0][1] + &left[0][1] * &right[1][1]],
+     [&left[1][0] * &right[0][0] + &left[1][1] * &right[1][0],
+      &left[1][0] * &right[0][1] + &left[1][1] * &right[1][1]]]
+}
+
+//Uses the identity
+//      | 1 1 |^n   | F(n + 1) F(n)     |
+//      | 1 0 |   = | F(n)     F(n - 1) |
+//and computes the power by repeated squaring, so only about 2 * log2(n) matrix products.
+impl FibonacciAlgorithm for MatrixExponentiation {
+    fn name(&self) -> &'static str{
+        "matrix"
+    }
+
+    fn complexity(&self) -> &'static str{
+        "O(log n)"
+    }
+
+    fn fibonacci(&self, index : usize) -> BigUint{
+        let zero = || BigUint::from(0u32);
+        let one = || BigUint::from(1u32);
+
+        let mut result : Matrix = [[one(), zero()], [zero(), one()]];
+        let mut base : Matrix = [[one(), one()], [one(), zero()]];
+        let mut exponent = index;
+
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = multiply(&result, &base);
+            }
+
+            base = multiply(&base, &base);
+            exponent >>= 1;
+        }
+
+        let [[_, value], _] = result;
+        value
+    }
+}
+
+//Computes the nth Fibonacci number with the fast doubling method, using these two identities:
+//      F(2k)     = F(k) * (2 * F(k + 1) - F(k))
+//      F(2k + 1) = F(k)^2 + F(k + 1)^2
+//Note - Going through the bits of the index from the most significant one, each step doubles k
+//          (and adds one when the bit is set), so it only takes about log2(index) steps.
+//       It is the matrix method with the redundant half of the matrix left out.
+pub fn fast_doubling(index : usize) -> BigUint{
+    //(F(k), F(k + 1)), starting from k = 0.
+    let mut current = BigUint::from(0u32);
+    let mut next = BigUint::from(1u32);
+
+    for bit in (0..usize::BITS - index.leading_zeros()).rev() {
+        let doubled = &current * (&next * 2u32 - &current);
+        let doubled_next = &current * &current + &next * &next;
+
+        if (index >> bit) & 1 == 1 {
+            next = &doubled + &doubled_next;
+            current = doubled_next;
+        } else {
+            current = doubled;
+            next = doubled_next;
+        }
+    }
+
+    current
+}
+
+impl FibonacciAlgorithm for FastDoubling {
+    fn name(&self) -> &'static str{
+        "doubling"
+    }
+
+    fn complexity(&self) -> &'static str{
+        "O(log n)"
+    }
+
+    fn fibonacci(&self, index : usize) -> BigUint{
+        fast_doubling(index)
+    }
+}
+
+//F(0) to F(186) straight from the compile time table, fast doubling past it.
+impl FibonacciAlgorithm for TableLookup {
+    fn name(&self) -> &'static str{
+        "table"
+    }
+
+    fn complexity(&self) -> &'static str{
+        "O(1), O(log n) past F(186)"
+    }
+
+    fn fibonacci(&self, index : usize) -> BigUint{
+        table::lookup(index).unwrap_or_else(|| fast_doubling(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_algorithms_agree(){
+        for index in 0..=25 {
+            let expected = fast_doubling(index);
+
+            for algorithm in ALGORITHMS.iter() {
+                assert_eq!(algorithm.fibonacci(index), expected, "{} at {}", algorithm.name(),
+                           index);
+            }
+        }
+
+        for index in [0, 186, 187, 1000] {
+            assert_eq!(TableLookup.fibonacci(index), fast_doubling(index));
+        }
+
+        //Memoized stays small, test threads have less stack than the main thread.
+        assert_eq!(Memoized.fibonacci(1000), fast_doubling(1000));
+
+        for index in [1000, 4095, 4096, 10_007] {
+            assert_eq!(Iterative.fibonacci(index), fast_doubling(index));
+            assert_eq!(MatrixExponentiation.fibonacci(index), fast_doubling(index));
+        }
+    }
+}
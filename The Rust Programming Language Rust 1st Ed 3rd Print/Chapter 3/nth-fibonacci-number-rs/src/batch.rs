@@ -0,0 +1,102 @@
+use std::io::{self, BufRead, Write};
+
+//Many indices at once, one per line, without stopping at the first bad one.
+//Note - Results go to the output and errors to the error output, so a CSV file stays valid even
+//          when some lines failed.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Text,
+    Csv,
+}
+
+pub fn parse_output_format(name : &str) -> Result<OutputFormat, String>{
+    match name.trim().to_lowercase().as_str() {
+        "text" => Ok(OutputFormat::Text),
+        "csv" => Ok(OutputFormat::Csv),
+        _ => Err(format!("Unknown output '{}', available outputs: text, csv", name.trim())),
+    }
+}
+
+pub struct Summary {
+    pub processed : usize,
+    pub failed : usize,
+}
+
+//Quotes a CSV field when it has to be, e.g. grouped digits with commas in them.
+fn csv_field(value : &str) -> String{
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+//Runs `compute` on every non empty line, which gives back the index and the formatted result.
+pub fn process(input : impl BufRead, output : &mut impl Write, errors : &mut impl Write,
+               format : OutputFormat, compute : impl Fn(&str) -> Result<(isize, String), String>)
+               -> io::Result<Summary>{
+    let mut summary = Summary { processed : 0, failed : 0 };
+
+    if format == OutputFormat::Csv {
+        writeln!(output, "index,value")?;
+    }
+
+    for (line_number, line) in input.lines().enumerate() {
+        let result = match line {
+            Ok(line) if line.trim().is_empty() => continue,
+            Ok(line) => compute(&line),
+            Err(error) => Err(format!("Failed to read line, {}", error)),
+        };
+        summary.processed += 1;
+
+        match (result, format) {
+            (Ok((index, value)), OutputFormat::Text) =>
+                writeln!(output, "F({}) = {}", index, value)?,
+            (Ok((index, value)), OutputFormat::Csv) =>
+                writeln!(output, "{},{}", index, csv_field(&value))?,
+            (Err(error), _) => {
+                summary.failed += 1;
+                writeln!(errors, "Line {}: {}", line_number + 1, error)?;
+            }
+        }
+    }
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(input : &str, format : OutputFormat) -> (String, String, usize, usize){
+        let (mut output, mut errors) = (Vec::new(), Vec::new());
+        let compute = |line : &str| -> Result<(isize, String), String> {
+            let index : isize = line.trim().parse()
+                .map_err(|_| format!("'{}' is not a valid index!", line.trim()))?;
+            Ok((index, format!("{},{}", index, index * 2)))
+        };
+
+        let summary = process(input.as_bytes(), &mut output, &mut errors, format, compute).unwrap();
+        (String::from_utf8(output).unwrap(), String::from_utf8(errors).unwrap(), summary.processed,
+         summary.failed)
+    }
+
+    #[test]
+    fn bad_lines_do_not_stop_the_batch(){
+        let (output, errors, processed, failed) = run("1\n\nx\n -3 \n", OutputFormat::Text);
+
+        assert_eq!(output, "F(1) = 1,2\nF(-3) = -3,-6\n");
+        assert_eq!(errors, "Line 3: 'x' is not a valid index!\n");
+        assert_eq!((processed, failed), (3, 1));
+    }
+
+    #[test]
+    fn csv_output(){
+        let (output, errors, _, failed) = run("2\n5", OutputFormat::Csv);
+
+        assert_eq!(output, "index,value\n2,\"2,4\"\n5,\"5,10\"\n");
+        assert_eq!((errors.as_str(), failed), ("", 0));
+        assert_eq!(csv_field("a\"b"), "\"a\"\"b\"");
+    }
+}
@@ -0,0 +1,83 @@
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use crate::algorithms::{FibonacciAlgorithm, ALGORITHMS};
+
+//Benchmark harness comparing the algorithms side by side.
+//Note - Each run is repeated until it adds up to at least MIN_SAMPLE_TIME, and the average is
+//          reported, so the fast algorithms are not just measuring the timer itself.
+//       Once an algorithm takes longer than SLOW_RUN_TIME at some index, the larger indices are
+//          skipped for it, which is how the O(2^n) one drops out long before the O(log n) ones.
+
+pub const DEFAULT_INDICES : [usize; 9] = [10, 20, 25, 30, 100, 1_000, 10_000, 100_000, 1_000_000];
+
+const MIN_SAMPLE_TIME : Duration = Duration::from_millis(20);
+const SLOW_RUN_TIME : Duration = Duration::from_millis(50);
+
+fn time_algorithm(algorithm : &dyn FibonacciAlgorithm, index : usize) -> Duration{
+    let started = Instant::now();
+    let mut runs : u32 = 0;
+
+    while runs == 0 || started.elapsed() < MIN_SAMPLE_TIME {
+        algorithm.fibonacci(index);
+        runs += 1;
+    }
+
+    started.elapsed() / runs
+}
+
+fn format_duration(duration : Duration) -> String{
+    let nanoseconds = duration.as_nanos();
+
+    if nanoseconds < 1_000 {
+        format!("{} ns", nanoseconds)
+    } else if nanoseconds < 1_000_000 {
+        format!("{:.1} us", nanoseconds as f64 / 1e3)
+    } else if nanoseconds < 1_000_000_000 {
+        format!("{:.1} ms", nanoseconds as f64 / 1e6)
+    } else {
+        format!("{:.2} s", nanoseconds as f64 / 1e9)
+    }
+}
+
+//`indices` should be in increasing order, for the skipping of slow algorithms to make sense.
+pub fn bench_output(indices : &[usize]){
+    println!("Average time per call, '-' means skipped (too slow or past the index limit).");
+    println!("Tip - run with --release, debug builds are many times slower.");
+    println!();
+
+    print!("{:>10}", "index");
+    for algorithm in ALGORITHMS.iter() {
+        print!(" {:>12}", algorithm.name());
+    }
+    println!();
+
+    print!("{:>10}", "");
+    for algorithm in ALGORITHMS.iter() {
+        print!(" {:>12}", algorithm.complexity());
+    }
+    println!();
+
+    let mut too_slow = [false; ALGORITHMS.len()];
+
+    for index in indices {
+        print!("{:>10}", index);
+
+        for (algorithm, too_slow) in ALGORITHMS.iter().zip(too_slow.iter_mut()) {
+            let over_limit = algorithm.index_limit().is_some_and(|limit| *index > limit);
+
+            if *too_slow || over_limit {
+                print!(" {:>12}", "-");
+                continue;
+            }
+
+            let duration = time_algorithm(*algorithm, *index);
+            *too_slow = duration > SLOW_RUN_TIME;
+            print!(" {:>12}", format_duration(duration));
+            //Printing as it goes, the slow cells can take a while.
+            io::stdout().flush().unwrap_or_default();
+        }
+
+        println!();
+    }
+}
@@ -0,0 +1,164 @@
+use num_bigint::BigUint;
+use num_traits::{FromPrimitive, One};
+
+use crate::sequence::Fibonacci;
+use crate::table;
+
+//Binet's formula F(n) = (φ^n - ψ^n) / √5, with φ = (1 + √5) / 2 and ψ = (1 - √5) / 2.
+//Note - Exact on paper, but φ^n grows just as fast as F(n), so once F(n) needs more significant
+//          bits than the floating point type has, the rounded result is off.
+//       f32 has 24 significant bits and f64 has 53, so they give out around F(30) and F(75), the
+//          exact index depends on how accurate powi is on the platform.
+//       |ψ^n / √5| < 1/2, so F(n) is simply φ^n / √5 rounded to the nearest integer, which
+//          the fixed point version uses to stay with unsigned numbers.
+
+pub const MIN_FIXED_POINT_BITS : u64 = 8;
+pub const MAX_FIXED_POINT_BITS : u64 = 4096;
+
+//Ratios printed by the binet mode, by then they are as close to φ as an f64 gets.
+pub const MAX_RATIO_INDEX : usize = 40;
+
+pub fn binet_f32(index : usize) -> f32{
+    let sqrt5 = 5f32.sqrt();
+    let phi = (1.0 + sqrt5) / 2.0;
+    let psi = (1.0 - sqrt5) / 2.0;
+
+    ((phi.powi(index as i32) - psi.powi(index as i32)) / sqrt5).round()
+}
+
+pub fn binet_f64(index : usize) -> f64{
+    let sqrt5 = 5f64.sqrt();
+    let phi = (1.0 + sqrt5) / 2.0;
+    let psi = (1.0 - sqrt5) / 2.0;
+
+    ((phi.powi(index as i32) - psi.powi(index as i32)) / sqrt5).round()
+}
+
+pub fn golden_ratio() -> f64{
+    (1.0 + 5f64.sqrt()) / 2.0
+}
+
+//Fixed point numbers with `bits` bits after the binary point, stored as value * 2^bits.
+pub struct FixedPoint {
+    bits : u64,
+    sqrt5 : BigUint,
+    phi : BigUint,
+}
+
+impl FixedPoint {
+    pub fn new(bits : u64) -> Result<FixedPoint, String>{
+        if !(MIN_FIXED_POINT_BITS..=MAX_FIXED_POINT_BITS).contains(&bits) {
+            return Err(format!("The number of bits has to be between {} and {}!",
+                               MIN_FIXED_POINT_BITS, MAX_FIXED_POINT_BITS));
+        }
+
+        //√(5 * 2^(2 * bits)) = √5 * 2^bits, rounded down.
+        let sqrt5 = (BigUint::from(5u32) << (2 * bits)).sqrt();
+        let phi = ((BigUint::one() << bits) + &sqrt5) >> 1;
+
+        Ok(FixedPoint { bits, sqrt5, phi })
+    }
+
+    fn multiply(&self, left : &BigUint, right : &BigUint) -> BigUint{
+        (left * right) >> self.bits
+    }
+
+    //φ^index / √5 rounded, with φ^index by repeated squaring.
+    pub fn binet(&self, index : usize) -> BigUint{
+        let mut power = BigUint::one() << self.bits;
+        let mut base = self.phi.clone();
+        let mut exponent = index;
+
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                power = self.multiply(&power, &base);
+            }
+
+            base = self.multiply(&base, &base);
+            exponent >>= 1;
+        }
+
+        let quotient = (power << self.bits) / &self.sqrt5;
+        (quotient + (BigUint::one() << (self.bits - 1))) >> self.bits
+    }
+}
+
+//First index where an approximation is not the exact Fibonacci number.
+pub struct Divergence {
+    pub index : usize,
+    //None when the approximation is not a finite number any more.
+    pub approximation : Option<BigUint>,
+    pub exact : BigUint,
+}
+
+pub fn first_divergence(approximation : impl Fn(usize) -> Option<BigUint>) -> Divergence{
+    for (index, exact) in Fibonacci::<BigUint>::new().enumerate() {
+        let approximation = approximation(index);
+
+        if approximation.as_ref() != Some(&exact) {
+            return Divergence { index, approximation, exact };
+        }
+    }
+
+    unreachable!("The big integer sequence never ends")
+}
+
+pub fn f32_divergence() -> Divergence{
+    first_divergence(|index| BigUint::from_f32(binet_f32(index)))
+}
+
+pub fn f64_divergence() -> Divergence{
+    first_divergence(|index| BigUint::from_f64(binet_f64(index)))
+}
+
+pub fn fixed_point_divergence(fixed_point : &FixedPoint) -> Divergence{
+    first_divergence(|index| Some(fixed_point.binet(index)))
+}
+
+//F(index + 1) / F(index) for index 1 to MAX_RATIO_INDEX, in f64.
+pub fn ratios() -> Vec<(usize, f64)>{
+    (1..=MAX_RATIO_INDEX)
+        .map(|index| (index, table::U64_TABLE[index + 1] as f64 / table::U64_TABLE[index] as f64))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms;
+
+    #[test]
+    fn floats_give_out_where_their_mantissa_does(){
+        let f32_divergence = f32_divergence();
+        assert!((25..=40).contains(&f32_divergence.index), "f32 at {}", f32_divergence.index);
+
+        //F(78) is the last one below 2^53.
+        let f64_divergence = f64_divergence();
+        assert!((65..=79).contains(&f64_divergence.index), "f64 at {}", f64_divergence.index);
+        assert_eq!(f64_divergence.exact, algorithms::fast_doubling(f64_divergence.index));
+    }
+
+    #[test]
+    fn more_bits_go_further(){
+        let divergence = |bits| fixed_point_divergence(&FixedPoint::new(bits).unwrap()).index;
+
+        let indices : Vec<usize> = [32, 64, 128, 256].into_iter().map(divergence).collect();
+        assert!(indices.windows(2).all(|pair| pair[0] < pair[1]), "{:?}", indices);
+        assert!(indices[1] > f64_divergence().index);
+
+        assert!(FixedPoint::new(4).is_err());
+    }
+
+    #[test]
+    fn ratios_close_in_on_phi(){
+        let errors : Vec<f64> = ratios().iter().map(|(_, ratio)| ratio - golden_ratio()).collect();
+
+        //Alternately above and below φ, closer every time.
+        for (index, pair) in errors.windows(2).take(15).enumerate() {
+            assert!(pair[0] * pair[1] < 0.0, "index {}", index + 1);
+            assert!(pair[1].abs() < pair[0].abs(), "index {}", index + 1);
+        }
+
+        assert!(errors.last().unwrap().abs() < 1e-15);
+    }
+}
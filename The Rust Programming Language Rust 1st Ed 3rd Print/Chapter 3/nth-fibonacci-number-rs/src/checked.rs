@@ -0,0 +1,144 @@
+use std::any;
+use std::fmt;
+
+use num_traits::PrimInt;
+
+use crate::algorithms::FibonacciAlgorithm;
+use crate::format::NumberFormat;
+use crate::negafibonacci;
+use crate::sequence::{self, Fibonacci, Selection};
+
+//Fibonacci numbers in fixed width integer types, with checked arithmetic so an index that does
+//not fit gives an Overflow error instead of a wrong number (release builds) or a panic (debug).
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overflow {
+    pub index : usize,
+    pub type_name : &'static str,
+    pub max_index : usize,
+}
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, formatter : &mut fmt::Formatter) -> fmt::Result{
+        write!(formatter, "F({}) does not fit in {}, the largest index it can hold is {}",
+               self.index, self.type_name, self.max_index)
+    }
+}
+
+pub fn fib<T : PrimInt>(index : usize) -> Result<T, Overflow>{
+    Fibonacci::<T>::new().nth(index).ok_or_else(|| Overflow {
+        index,
+        type_name : any::type_name::<T>(),
+        max_index : max_index::<T>(),
+    })
+}
+
+//Largest index whose Fibonacci number fits in T, e.g. 46 for i32, 93 for u64 and 186 for u128.
+pub fn max_index<T : PrimInt>() -> usize{
+    //The sequence ends with the last term that fits, and starts at index 0.
+    Fibonacci::<T>::new().count() - 1
+}
+
+//F(index) for any signed index, negative numbers only fit in the signed types.
+fn fib_string<T : PrimInt + fmt::Display>(index : isize) -> Result<String, String>{
+    let value = fib::<T>(index.unsigned_abs()).map_err(|overflow| if index < 0 {
+        format!("F({}) does not fit in {}, the largest negative index it can hold is -{}", index,
+                overflow.type_name, overflow.max_index)
+    } else {
+        overflow.to_string()
+    })?;
+
+    if !negafibonacci::is_negative_term(index) {
+        return Ok(value.to_string());
+    }
+
+    T::zero().checked_sub(&value).map(|value| value.to_string()).ok_or_else(|| format!(
+        "F({}) = -{} is negative, which does not fit in {}", index, value, any::type_name::<T>()))
+}
+
+pub struct IntegerType {
+    pub name : &'static str,
+    pub max_index : fn() -> usize,
+    pub fib : fn(isize) -> Result<String, String>,
+    pub print_terms : fn(&Selection, &str, &dyn FibonacciAlgorithm, NumberFormat)
+                         -> Result<(), String>,
+}
+
+macro_rules! integer_types {
+    ($($type:ty),*) => {
+        [$(IntegerType { name : stringify!($type), max_index : max_index::<$type>,
+                         fib : fib_string::<$type>,
+                         print_terms : sequence::print_terms::<$type> }),*]
+    };
+}
+
+//Ordered from the smallest to the largest, unsigned types before signed ones of the same width.
+pub const INTEGER_TYPES : [IntegerType; 12] = integer_types!(u8, i8, u16, i16, u32, i32, u64, i64,
+    usize, isize, u128, i128);
+
+impl IntegerType {
+    pub fn is_signed(&self) -> bool{
+        self.name.starts_with('i')
+    }
+}
+
+pub fn find_integer_type(name : &str) -> Option<&'static IntegerType>{
+    INTEGER_TYPES.iter().find(|integer_type| integer_type.name == name.trim())
+}
+
+pub fn integer_type_names() -> String{
+    INTEGER_TYPES.iter().map(|integer_type| integer_type.name).collect::<Vec<_>>().join(", ")
+}
+
+//Smallest unsigned and signed types that can hold F(index), None when even 128 bits are too few.
+pub fn smallest_types(index : usize) -> (Option<&'static str>, Option<&'static str>){
+    let smallest = |signed : bool| INTEGER_TYPES.iter()
+        .filter(|integer_type| integer_type.is_signed() == signed)
+        .find(|integer_type| (integer_type.max_index)() >= index)
+        .map(|integer_type| integer_type.name);
+
+    (smallest(false), smallest(true))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn largest_indices_per_type(){
+        assert_eq!(max_index::<u8>(), 13);
+        assert_eq!(max_index::<i8>(), 11);
+        assert_eq!(max_index::<i32>(), 46);
+        assert_eq!(max_index::<u32>(), 47);
+        assert_eq!(max_index::<u64>(), 93);
+        assert_eq!(max_index::<i128>(), 184);
+        assert_eq!(max_index::<u128>(), 186);
+    }
+
+    #[test]
+    fn overflow_is_reported_right_past_the_limit(){
+        assert_eq!(fib::<u8>(13), Ok(233));
+        assert_eq!(fib::<u8>(14), Err(Overflow { index : 14, type_name : "u8", max_index : 13 }));
+        assert_eq!(fib::<i32>(46), Ok(1_836_311_903));
+        assert!(fib::<i32>(47).is_err());
+        assert_eq!(fib::<u128>(186), Ok(332_825_110_087_067_562_321_196_029_789_634_457_848));
+        assert!(fib::<u128>(187).is_err());
+    }
+
+    #[test]
+    fn smallest_type_for_an_index(){
+        assert_eq!(smallest_types(20), (Some("u16"), Some("i16")));
+        assert_eq!(smallest_types(47), (Some("u32"), Some("i64")));
+        assert_eq!(smallest_types(187), (None, None));
+    }
+
+    #[test]
+    fn negative_indices_need_a_signed_type(){
+        assert_eq!(fib_string::<i8>(-11), Ok(String::from("89")));
+        assert_eq!(fib_string::<i8>(-10), Ok(String::from("-55")));
+        assert_eq!(fib_string::<u8>(-13), Ok(String::from("233")));
+        assert!(fib_string::<u8>(-2).is_err());
+        assert!(fib_string::<i8>(-12).is_err());
+        assert_eq!(fib_string::<i128>(-184).map(|value| value.starts_with('-')), Ok(true));
+    }
+}
@@ -0,0 +1,185 @@
+use num_bigint::{BigInt, BigUint, Sign};
+use num_traits::Zero;
+
+//Ways of writing out a (possibly huge) result.
+//Note - F(1_000_000) has 208_988 digits nobody is going to read, so past MAX_AUTO_DIGITS the
+//          default is scientific notation.
+//       The digit count and the scientific notation never write the whole number out in decimal,
+//          a power of ten and one division are enough.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumberFormat {
+    //Decimal up to MAX_AUTO_DIGITS digits, scientific past that.
+    Auto,
+    Decimal,
+    //Decimal with a comma between every group of three digits.
+    Grouped,
+    Hexadecimal,
+    //With the given number of significant digits, rounded.
+    Scientific(usize),
+    DigitCount,
+}
+
+pub const MAX_AUTO_DIGITS : usize = 1_000;
+pub const DEFAULT_SIGNIFICANT_DIGITS : usize = 10;
+
+pub const FORMAT_NAMES : &str = "auto, decimal, grouped, hex, scientific, digits";
+
+//Usage: --format <auto | decimal | grouped | hex | scientific | digits> [--significant N]
+pub fn parse_format(name : &str, significant_digits : Option<usize>)
+                    -> Result<NumberFormat, String>{
+    let format = match name.trim().to_lowercase().as_str() {
+        "auto" => NumberFormat::Auto,
+        "decimal" => NumberFormat::Decimal,
+        "grouped" => NumberFormat::Grouped,
+        "hex" => NumberFormat::Hexadecimal,
+        "scientific" => NumberFormat::Scientific(significant_digits
+            .unwrap_or(DEFAULT_SIGNIFICANT_DIGITS)),
+        "digits" => NumberFormat::DigitCount,
+        _ => return Err(format!("Unknown format '{}', available formats: {}", name.trim(),
+                                FORMAT_NAMES)),
+    };
+
+    match (format, significant_digits) {
+        (NumberFormat::Scientific(0), _) =>
+            Err(String::from("--significant has to be at least 1!")),
+        (NumberFormat::Scientific(_), _) | (_, None) => Ok(format),
+        _ => Err(String::from("--significant only applies to --format scientific!")),
+    }
+}
+
+//Number of decimal digits, without writing the number out.
+pub fn decimal_digits(number : &BigUint) -> usize{
+    if number.is_zero() {
+        return 1;
+    }
+
+    //2^(bits - 1) <= number < 2^bits, so this is either right or one too many.
+    let estimate = (number.bits() as f64 * 2f64.log10()).floor() as usize + 1;
+
+    if *number < BigUint::from(10u32).pow(estimate as u32 - 1) {
+        estimate - 1
+    } else {
+        estimate
+    }
+}
+
+fn sign_prefix(number : &BigInt) -> &'static str{
+    if number.sign() == Sign::Minus { "-" } else { "" }
+}
+
+fn grouped(digits : &str) -> String{
+    let mut result = String::new();
+
+    for (position, digit) in digits.chars().enumerate() {
+        if position > 0 && (digits.len() - position).is_multiple_of(3) {
+            result.push(',');
+        }
+        result.push(digit);
+    }
+
+    result
+}
+
+//d.ddd...e<exponent> like {:.N e} does for floating point numbers, always with exactly
+//significant_digits digits.
+fn scientific(number : &BigUint, significant_digits : usize) -> String{
+    let digits = decimal_digits(number);
+    let mut exponent = digits - 1;
+
+    let leading = if digits <= significant_digits {
+        //Nothing to round, only zeros to add.
+        format!("{}{}", number, "0".repeat(significant_digits - digits))
+    } else {
+        //One digit more than wanted, to round on.
+        let leading = number / BigUint::from(10u32).pow((digits - significant_digits - 1) as u32);
+        let rounded = (leading + 5u32) / 10u32;
+        let mut rounded = rounded.to_string();
+
+        //Rounding 99...9 up gives one more digit.
+        if rounded.len() > significant_digits {
+            rounded.pop();
+            exponent += 1;
+        }
+
+        rounded
+    };
+
+    match leading.split_at(1) {
+        (first, "") => format!("{}e{}", first, exponent),
+        (first, rest) => format!("{}.{}e{}", first, rest, exponent),
+    }
+}
+
+pub fn format_number(number : &BigInt, format : NumberFormat) -> String{
+    let magnitude = number.magnitude();
+
+    match format {
+        NumberFormat::Auto if decimal_digits(magnitude) > MAX_AUTO_DIGITS =>
+            format_number(number, NumberFormat::Scientific(DEFAULT_SIGNIFICANT_DIGITS)),
+        NumberFormat::Auto | NumberFormat::Decimal => number.to_string(),
+        NumberFormat::Grouped => format!("{}{}", sign_prefix(number),
+                                         grouped(&magnitude.to_string())),
+        NumberFormat::Hexadecimal => format!("{}0x{:x}", sign_prefix(number), magnitude),
+        NumberFormat::Scientific(significant_digits) => format!("{}{}", sign_prefix(number),
+            scientific(magnitude, significant_digits)),
+        NumberFormat::DigitCount => decimal_digits(magnitude).to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digit_counts_around_powers_of_ten(){
+        assert_eq!(decimal_digits(&BigUint::zero()), 1);
+
+        for exponent in 0..400u32 {
+            let power = BigUint::from(10u32).pow(exponent);
+            for number in [&power - 1u32, power.clone(), power + 1u32] {
+                assert_eq!(decimal_digits(&number), number.to_string().len(), "{}", number);
+            }
+        }
+    }
+
+    #[test]
+    fn formats(){
+        let format = |number : i64, format : NumberFormat| format_number(&BigInt::from(number),
+                                                                         format);
+
+        assert_eq!(format(-1234567, NumberFormat::Decimal), "-1234567");
+        assert_eq!(format(-1234567, NumberFormat::Grouped), "-1,234,567");
+        assert_eq!(format(123456, NumberFormat::Grouped), "123,456");
+        assert_eq!(format(6765, NumberFormat::Hexadecimal), "0x1a6d");
+        assert_eq!(format(-8, NumberFormat::Hexadecimal), "-0x8");
+        assert_eq!(format(6765, NumberFormat::DigitCount), "4");
+
+        assert_eq!(format(6765, NumberFormat::Scientific(10)), "6.765000000e3");
+        assert_eq!(format(6765, NumberFormat::Scientific(4)), "6.765e3");
+        assert_eq!(format(-8, NumberFormat::Scientific(3)), "-8.00e0");
+        assert_eq!(format(6765, NumberFormat::Scientific(2)), "6.8e3");
+        assert_eq!(format(6765, NumberFormat::Scientific(1)), "7e3");
+        assert_eq!(format(-999_999, NumberFormat::Scientific(3)), "-1.00e6");
+        assert_eq!(format(0, NumberFormat::Scientific(3)), "0.00e0");
+        assert_eq!(format(0, NumberFormat::Scientific(1)), "0e0");
+    }
+
+    #[test]
+    fn auto_switches_to_scientific(){
+        let digits = |count : usize| format_number(&"9".repeat(count).parse().unwrap(),
+                                                   NumberFormat::Auto);
+
+        assert_eq!(digits(MAX_AUTO_DIGITS), "9".repeat(MAX_AUTO_DIGITS));
+        assert_eq!(digits(MAX_AUTO_DIGITS + 1), format!("1.000000000e{}", MAX_AUTO_DIGITS + 1));
+    }
+
+    #[test]
+    fn significant_digits_option(){
+        assert_eq!(parse_format("scientific", Some(3)), Ok(NumberFormat::Scientific(3)));
+        assert_eq!(parse_format("Scientific", None), Ok(NumberFormat::Scientific(10)));
+        assert!(parse_format("scientific", Some(0)).is_err());
+        assert!(parse_format("hex", Some(3)).is_err());
+        assert!(parse_format("roman", None).is_err());
+    }
+}
@@ -0,0 +1,105 @@
+use num_bigint::BigUint;
+
+use crate::sequence::Fibonacci;
+
+//Going from a number back to the sequence.
+//Note - Every positive integer is a sum of distinct, non-consecutive Fibonacci numbers in exactly
+//          one way (Zeckendorf's theorem), found greedily by always taking the largest one that
+//          still fits. For example 100 = 89 + 8 + 3.
+
+pub struct Lookup {
+    //Index of the number in the sequence, the smallest one for 1 = F(1) = F(2).
+    pub index : Option<usize>,
+    //Largest Fibonacci number strictly below the number, None for 0.
+    pub below : Option<(usize, BigUint)>,
+    //Smallest Fibonacci number strictly above the number.
+    pub above : (usize, BigUint),
+}
+
+//Terms F(0), F(1), ... that are not larger than `number`.
+fn terms_up_to(number : &BigUint) -> Vec<(usize, BigUint)>{
+    Fibonacci::<BigUint>::new().enumerate().take_while(|(_, term)| term <= number).collect()
+}
+
+pub fn lookup(number : &BigUint) -> Lookup{
+    let mut below = None;
+    let mut index = None;
+
+    for (term_index, term) in Fibonacci::<BigUint>::new().enumerate() {
+        if term > *number {
+            return Lookup { index, below, above : (term_index, term) };
+        }
+
+        if term == *number {
+            index = index.or(Some(term_index));
+        } else {
+            below = Some((term_index, term));
+        }
+    }
+
+    unreachable!("The big integer sequence never ends")
+}
+
+//Zeckendorf representation, as (index, F(index)) from the largest term down, empty for 0.
+pub fn zeckendorf(number : &BigUint) -> Vec<(usize, BigUint)>{
+    let mut remainder = number.clone();
+    let mut parts = Vec::new();
+
+    //Skips F(0) and F(1), so each number is only used once (F(2) is the 1).
+    for (index, term) in terms_up_to(number).into_iter().skip(2).rev() {
+        if term <= remainder {
+            remainder -= &term;
+            parts.push((index, term));
+        }
+    }
+
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn membership_matches_the_sequence(){
+        let terms : Vec<u32> = Fibonacci::<u32>::new().take_while(|term| *term <= 1000).collect();
+
+        for number in 0u32..=1000 {
+            let result = lookup(&BigUint::from(number));
+            assert_eq!(result.index.is_some(), terms.contains(&number), "number {}", number);
+
+            if let Some(index) = result.index {
+                assert_eq!(terms[index], number);
+            }
+
+            assert!(result.above.1 > BigUint::from(number));
+            assert!(result.below.map_or(number == 0, |(_, below)| below < BigUint::from(number)));
+        }
+
+        assert_eq!(lookup(&BigUint::from(1u32)).index, Some(1));
+        assert_eq!(lookup(&BigUint::from(6765u32)).index, Some(20));
+    }
+
+    #[test]
+    fn zeckendorf_representations(){
+        let terms = |number : u32| -> Vec<u32> {
+            zeckendorf(&BigUint::from(number)).iter()
+                .map(|(_, term)| term.to_u32_digits().first().copied().unwrap_or_default())
+                .collect()
+        };
+
+        assert_eq!(terms(0), Vec::<u32>::new());
+        assert_eq!(terms(1), [1]);
+        assert_eq!(terms(4), [3, 1]);
+        assert_eq!(terms(100), [89, 8, 3]);
+
+        for number in 1u32..=3000 {
+            let parts = zeckendorf(&BigUint::from(number));
+            let sum : BigUint = parts.iter().map(|(_, term)| term).sum();
+            assert_eq!(sum, BigUint::from(number));
+
+            //No two consecutive indices.
+            assert!(parts.windows(2).all(|pair| pair[0].0 >= pair[1].0 + 2), "number {}", number);
+        }
+    }
+}
@@ -1,33 +1,528 @@
-use std::io;
+use std::env;
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::process;
 
-fn fibonacci_number_list(index : usize) -> i32{
-    //First 20 fibonacci number (Including the 0 index one)
-    let fibonacci_number_list : [i32; 21] = [0, 1, 1, 2, 3, 5,
-        8, 13, 21, 34, 55, 89, 144, 233, 377, 610, 987, 1597, 2584, 4181, 6765];
-    return fibonacci_number_list[index];
+use num_bigint::{BigInt, Sign};
+
+use algorithms::FibonacciAlgorithm;
+
+mod algorithms;
+mod batch;
+mod bench;
+mod binet;
+mod checked;
+mod format;
+mod inverse;
+mod modular;
+mod negafibonacci;
+mod recurrence;
+mod sequence;
+mod table;
+mod verify;
+
+//Negative indices use F(-n) = (-1)^(n + 1) * F(n), so the algorithms only ever see n.
+fn fibonacci_number(algorithm : &dyn FibonacciAlgorithm, index : isize) -> Result<BigInt, String>{
+    let magnitude = index.unsigned_abs();
+    algorithms::check_index_limit(algorithm, magnitude)?;
+
+    Ok(negafibonacci::with_sign(index, algorithm.fibonacci(magnitude)))
 }
 
-fn main() {
-    //TODO: Generating nth fibonacci numbers instead of hard coding, and using array would be more
-    //      elegant and flexible, no?
-    //      Be sure to keep in mind of i32 limitations though, maybe change to u32 instead of i32
-    //      while at it?
-    println!("Welcome to nth Fibonacci Number program!");
-    println!("Note - Only index in range of 0 to 20 inclusive is supported.");
-    println!("Please choose the index you want for the Fibonacci number calculation.");
-
-    let mut fibonacci_index = String::new();
-    io::stdin().read_line(&mut fibonacci_index)
+//Which primitive integer types are big enough for F(index).
+fn type_hint(index : isize) -> String{
+    let (unsigned, signed) = checked::smallest_types(index.unsigned_abs());
+    let unsigned = unsigned.filter(|_| !negafibonacci::is_negative_term(index));
+
+    match (unsigned, signed) {
+        (Some(unsigned), Some(signed)) => format!("It fits in {} (or {} if it has to be \
+            signed).", unsigned, signed),
+        (Some(unsigned), None) => format!("It fits in {}, no signed type is big enough.",
+                                          unsigned),
+        (None, Some(signed)) => format!("It fits in {}, no unsigned type can hold a negative \
+            number.", signed),
+        _ => String::from("It does not fit in any primitive integer type (u128 stops at index \
+            186), an arbitrary precision integer like BigInt is needed."),
+    }
+}
+
+//Usage: --type <u8 | i8 | ... | u128 | i128>
+fn fibonacci_number_of_type(type_name : &str, index : isize) -> Result<String, String>{
+    let integer_type = checked::find_integer_type(type_name).ok_or(format!(
+        "Unknown type '{}', available types: {}", type_name, checked::integer_type_names()))?;
+
+    (integer_type.fib)(index).map_err(|error| format!("{}. {}", error, type_hint(index)))
+}
+
+//F(index) with the chosen algorithm, or in the chosen primitive type.
+fn fibonacci_result(algorithm : &dyn FibonacciAlgorithm, integer_type : Option<&str>, index : isize)
+                    -> Result<BigInt, String>{
+    match integer_type {
+        Some(type_name) => Ok(fibonacci_number_of_type(type_name, index)?.parse()
+            .expect("Primitive integers always print as valid integers")),
+        None => fibonacci_number(algorithm, index),
+    }
+}
+
+//Removes `--name value` from the arguments wherever it is, returning the value if it was given.
+fn take_option(arguments : &mut Vec<String>, name : &str) -> Result<Option<String>, String>{
+    match arguments.iter().position(|argument| argument == name) {
+        None => Ok(None),
+        Some(index) if index + 1 < arguments.len() => {
+            let value = arguments.remove(index + 1);
+            arguments.remove(index);
+            Ok(Some(value))
+        }
+        Some(_) => Err(format!("Missing value for {}", name)),
+    }
+}
+
+fn parse_index(value : &str) -> Result<usize, String>{
+    value.trim().parse().map_err(|_| format!("'{}' is not a valid index!", value.trim()))
+}
+
+fn parse_signed_index(value : &str) -> Result<isize, String>{
+    value.trim().parse().map_err(|_| format!("'{}' is not a valid index!", value.trim()))
+}
+
+//Usage: --from A --to B | --to B | --first N | --below BOUND
+fn take_selection(arguments : &mut Vec<String>) -> Result<Option<sequence::Selection>, String>{
+    let from = take_option(arguments, "--from")?;
+    let to = take_option(arguments, "--to")?;
+    let first = take_option(arguments, "--first")?;
+    let below = take_option(arguments, "--below")?;
+
+    let selection = match (from, to, first, below) {
+        (None, None, None, None) => return Ok(None),
+        (from, Some(to), None, None) => {
+            let from = match from {
+                Some(from) => parse_signed_index(&from)?,
+                None => 0,
+            };
+            let to = parse_signed_index(&to)?;
+
+            if from > to {
+                return Err(format!("Invalid range, --from {} is past --to {}!", from, to));
+            }
+            sequence::Selection::Range(from, to)
+        }
+        (None, None, Some(first), None) => sequence::Selection::First(parse_index(&first)?),
+        (None, None, None, Some(below)) => sequence::Selection::Below(below.trim().parse()
+            .map_err(|_| format!("'{}' is not a valid bound!", below.trim()))?),
+        (Some(_), None, None, None) => return Err(String::from("--from also needs --to!")),
+        _ => return Err(String::from("Please only choose one of --from/--to, --first or --below!")),
+    };
+
+    Ok(Some(selection))
+}
+
+//Usage: nth-fibonacci-number-rs bench [index...]
+fn bench_mode(arguments : &[String]) -> Result<(), String>{
+    let mut indices = if arguments.is_empty() {
+        bench::DEFAULT_INDICES.to_vec()
+    } else {
+        arguments.iter().map(|argument| parse_index(argument)).collect::<Result<Vec<_>, _>>()?
+    };
+
+    indices.sort_unstable();
+    bench::bench_output(&indices);
+    Ok(())
+}
+
+fn divergence_output(name : &str, divergence : &binet::Divergence) -> String{
+    let approximation = match &divergence.approximation {
+        Some(approximation) => approximation.to_string(),
+        None => String::from("infinity"),
+    };
+
+    format!("{}: exact up to F({}), F({}) comes out as {} instead of {}.", name,
+            divergence.index - 1, divergence.index, approximation, divergence.exact)
+}
+
+//Usage: nth-fibonacci-number-rs binet [--bits N]
+fn binet_mode(mut arguments : Vec<String>) -> Result<(), String>{
+    let fixed_point = match take_option(&mut arguments, "--bits")? {
+        Some(bits) => Some(binet::FixedPoint::new(bits.trim().parse()
+            .map_err(|_| format!("'{}' is not a valid number of bits!", bits.trim()))?)
+            .map(|fixed_point| (bits.trim().to_string(), fixed_point))?),
+        None => None,
+    };
+
+    if !arguments.is_empty() {
+        return Err(String::from("Usage: binet [--bits N]"));
+    }
+
+    println!("Binet's formula F(n) = (φ^n - ψ^n) / √5 against the exact Fibonacci numbers:");
+    println!("{}", divergence_output("f32", &binet::f32_divergence()));
+    println!("{}", divergence_output("f64", &binet::f64_divergence()));
+
+    if let Some((bits, fixed_point)) = fixed_point {
+        println!("{}", divergence_output(&format!("Fixed point with {} bits", bits),
+                                         &binet::fixed_point_divergence(&fixed_point)));
+    }
+
+    println!();
+    println!("F(n + 1) / F(n) converging to φ = {}:", binet::golden_ratio());
+    for (index, ratio) in binet::ratios() {
+        println!("{:>4} {:<20} {:+.3e}", index, ratio, ratio - binet::golden_ratio());
+    }
+
+    Ok(())
+}
+
+//Residues printed by the pisano mode before the rest is left out.
+const MAX_PRINTED_RESIDUES : u64 = 1_000;
+
+fn parse_modulus(value : Option<&String>) -> Result<u64, String>{
+    let value = value.ok_or("Missing modulus!")?;
+
+    match value.trim().parse() {
+        Ok(modulus) if modulus > 0 => Ok(modulus),
+        _ => Err(format!("'{}' is not a valid modulus, please enter a number of at least 1!",
+                         value.trim())),
+    }
+}
+
+//Usage: nth-fibonacci-number-rs mod <index> <modulus>
+fn mod_mode(arguments : &[String]) -> Result<(), String>{
+    let index = arguments.first().ok_or("Missing index, usage: mod <index> <modulus>")?;
+    let modulus = parse_modulus(arguments.get(1))?;
+    let residue = match index.trim().parse::<u128>() {
+        Ok(index) => modular::fib_mod(index, modulus),
+        //Past u128, or not a number at all, which fib_mod_decimal reports.
+        Err(_) => modular::fib_mod_decimal(index, modulus)?,
+    };
+
+    println!("F({}) mod {} = {}", index.trim(), modulus, residue);
+    Ok(())
+}
+
+//Usage: nth-fibonacci-number-rs pisano <modulus>
+fn pisano_mode(arguments : &[String]) -> Result<(), String>{
+    let modulus = parse_modulus(arguments.first())?;
+    let period = modular::pisano_period(modulus)?;
+    let residues = modular::pisano_residues(modulus, period.min(MAX_PRINTED_RESIDUES));
+
+    println!("The Pisano period of {} is {}.", modulus, period);
+    println!("Repeating residues F(n) mod {}:", modulus);
+
+    let residues : Vec<String> = residues.iter().map(|residue| residue.to_string()).collect();
+    if period > MAX_PRINTED_RESIDUES {
+        println!("{}, ... ({} more)", residues.join(", "), period - MAX_PRINTED_RESIDUES);
+    } else {
+        println!("{}", residues.join(", "));
+    }
+
+    Ok(())
+}
+
+//Usage: nth-fibonacci-number-rs verify [--from A --to B | --first N]
+fn verify_mode(algorithm : &dyn FibonacciAlgorithm, selection : Option<sequence::Selection>)
+               -> Result<(), String>{
+    let (from, to) = match selection {
+        None => verify::DEFAULT_RANGE,
+        Some(sequence::Selection::Range(from, to)) => (from, to),
+        Some(sequence::Selection::First(count)) if count > 0 => (0, count as isize - 1),
+        Some(_) => return Err(String::from("Please choose the range to verify with --from/--to \
+            or --first!")),
+    };
+
+    println!("Checking identities from F({}) to F({}) with the {} algorithm.", from, to,
+             algorithm.name());
+
+    let results = verify::verify(from, to, |index| fibonacci_number(algorithm, index))?;
+    let mut mismatches = 0;
+
+    for result in &results {
+        if result.checks == 0 {
+            println!("{}: nothing to check in this range.", result.name);
+            continue;
+        }
+
+        if result.mismatches == 0 {
+            println!("{}: all {} checks passed.", result.name, result.checks);
+            continue;
+        }
+
+        println!("{}: {} of {} checks failed!", result.name, result.mismatches, result.checks);
+        for mismatch in &result.reported {
+            println!("    {}", mismatch);
+        }
+        if result.mismatches > result.reported.len() {
+            println!("    ... ({} more)", result.mismatches - result.reported.len());
+        }
+
+        mismatches += result.mismatches;
+    }
+
+    match mismatches {
+        0 => Ok(()),
+        _ => Err(format!("{} identity checks failed!", mismatches)),
+    }
+}
+
+//Usage: nth-fibonacci-number-rs batch [file | -] [--output text | csv]
+fn batch_mode(mut arguments : Vec<String>, algorithm : &dyn FibonacciAlgorithm,
+              integer_type : Option<&str>, number_format : format::NumberFormat)
+              -> Result<(), String>{
+    let output_format = match take_option(&mut arguments, "--output")? {
+        Some(name) => batch::parse_output_format(&name)?,
+        None => batch::OutputFormat::Text,
+    };
+
+    let compute = |line : &str| -> Result<(isize, String), String> {
+        let index = parse_signed_index(line)?;
+        let result = fibonacci_result(algorithm, integer_type, index)?;
+        Ok((index, format::format_number(&result, number_format)))
+    };
+
+    let (mut output, mut errors) = (io::stdout().lock(), io::stderr().lock());
+    let summary = match arguments.as_slice() {
+        [] => batch::process(io::stdin().lock(), &mut output, &mut errors, output_format, compute),
+        [path] if path == "-" => batch::process(io::stdin().lock(), &mut output, &mut errors,
+                                                output_format, compute),
+        [path] => {
+            let file = File::open(path).map_err(|error| format!("Failed to open '{}', {}", path,
+                                                                 error))?;
+            batch::process(BufReader::new(file), &mut output, &mut errors, output_format, compute)
+        }
+        _ => return Err(String::from("Usage: batch [file | -] [--output text | csv]")),
+    }.map_err(|error| format!("Failed to write the results, {}", error))?;
+
+    match summary.failed {
+        0 => Ok(()),
+        failed => Err(format!("{} of {} lines failed!", failed, summary.processed)),
+    }
+}
+
+fn read_input_line() -> String{
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)
         .expect("Failed to read line, please try again!");
-    let fibonacci_index: usize = fibonacci_index.trim().parse()
-        .expect("Not a number, please try again!");
+    input
+}
+
+//Usage: nth-fibonacci-number-rs lookup [number]
+fn lookup_mode(arguments : &[String]) -> Result<(), String>{
+    let number = match arguments.first() {
+        Some(number) => number.clone(),
+        None => {
+            println!("Please enter the number you want to look up in the Fibonacci sequence.");
+            read_input_line()
+        }
+    };
+
+    let number : BigInt = number.trim().parse()
+        .map_err(|_| format!("'{}' is not a valid integer!", number.trim()))?;
 
-    //No need to do fibonacci_index >= 0 due to type limit (usize cannot be less than 0)
-    if fibonacci_index <= 20{
-        let results = fibonacci_number_list(fibonacci_index);
-        println!("The Fibonacci number for index {} is {}!", fibonacci_index, results);
+    //Negative numbers only show up at even negative indices, -1 = F(-2) and -F(k) = F(-k).
+    if number.sign() == Sign::Minus {
+        match inverse::lookup(number.magnitude()).index {
+            Some(1) => println!("{} is a Fibonacci number, F(-2) = {}", number, number),
+            Some(index) if index % 2 == 0 => println!("{} is a Fibonacci number, F(-{}) = {}",
+                                                      number, index, number),
+            _ => println!("{} is not a Fibonacci number, only the even negative indices give \
+                negative ones.", number),
+        }
+        return Ok(());
     }
-    else {
-        println!("Invalid index, please enter index between 0 and 20 inclusive!")
+
+    let number = number.magnitude();
+    let result = inverse::lookup(number);
+
+    match result.index {
+        Some(index) => println!("{} is a Fibonacci number, F({}) = {}", number, index, number),
+        None => println!("{} is not a Fibonacci number.", number),
+    }
+
+    if let Some((index, below)) = &result.below {
+        println!("Nearest Fibonacci number below: F({}) = {}", index, below);
+    }
+    println!("Nearest Fibonacci number above: F({}) = {}", result.above.0, result.above.1);
+
+    let parts = inverse::zeckendorf(number);
+    if !parts.is_empty() {
+        let sum : Vec<String> = parts.iter().map(|(_, term)| term.to_string()).collect();
+        let indices : Vec<String> = parts.iter().map(|(index, _)| format!("F({})", index))
+            .collect();
+        println!("Zeckendorf representation: {} = {} = {}", number, sum.join(" + "),
+                 indices.join(" + "));
+    }
+
+    Ok(())
+}
+
+fn parse_integer_list(value : &str) -> Result<Vec<BigInt>, String>{
+    value.split(',')
+        .map(|item| item.trim().parse()
+            .map_err(|_| format!("'{}' is not a valid integer!", item.trim())))
+        .collect()
+}
+
+//Usage: nth-fibonacci-number-rs recurrence <preset> <index>
+//       nth-fibonacci-number-rs recurrence <index> --coefficients c1,c2,... --seeds a0,a1,...
+fn recurrence_mode(mut arguments : Vec<String>) -> Result<(), String>{
+    let coefficients = take_option(&mut arguments, "--coefficients")?;
+    let seeds = take_option(&mut arguments, "--seeds")?;
+
+    let (recurrence, index) = match (coefficients, seeds, arguments.as_slice()) {
+        (Some(coefficients), Some(seeds), [index]) => (recurrence::LinearRecurrence::new(
+            "custom", parse_integer_list(&coefficients)?, parse_integer_list(&seeds)?)?, index),
+        (None, None, [preset, index]) => (recurrence::LinearRecurrence::preset(preset).ok_or(
+            format!("Unknown preset '{}', available presets: {}", preset,
+                    recurrence::PRESET_NAMES))?, index),
+        _ => return Err(String::from("Usage: recurrence <preset> <index>, or recurrence <index> \
+            --coefficients c1,c2,... --seeds a0,a1,...")),
+    };
+
+    let index = parse_index(index)?;
+    let first_terms : Vec<String> = recurrence.terms(10).iter().map(|term| term.to_string())
+        .collect();
+
+    println!("The {} sequence starts with {}, ...", recurrence.name, first_terms.join(", "));
+    println!("Term {} is {}!", index, recurrence.nth(index));
+    Ok(())
+}
+
+fn run(mut arguments : Vec<String>) -> Result<(), String>{
+    let chosen_algorithm = take_option(&mut arguments, "--algorithm")?
+        .map(|name| algorithms::find_algorithm(&name).ok_or(format!(
+            "Unknown algorithm '{}', available algorithms: {}", name,
+            algorithms::algorithm_names()))).transpose()?;
+    //Without --algorithm small indices come from the compile time table, an algorithm that was
+    //asked for always runs.
+    let algorithm = chosen_algorithm.unwrap_or(&algorithms::TableLookup);
+
+    let integer_type = take_option(&mut arguments, "--type")?;
+    let index = take_option(&mut arguments, "--index")?;
+    let significant_digits = take_option(&mut arguments, "--significant")?
+        .map(|digits| digits.trim().parse().map_err(|_| format!("'{}' is not a valid number of \
+            significant digits!", digits.trim()))).transpose()?;
+    let number_format = match take_option(&mut arguments, "--format")? {
+        Some(name) => format::parse_format(&name, significant_digits)?,
+        None => format::parse_format("auto", significant_digits)?,
+    };
+    let selection = take_selection(&mut arguments)?;
+
+    if !arguments.is_empty() {
+        let mode = arguments.remove(0);
+        return match mode.as_str() {
+            "batch" => batch_mode(arguments, algorithm, integer_type.as_deref(), number_format),
+            "bench" => bench_mode(&arguments),
+            "binet" => binet_mode(arguments),
+            "mod" => mod_mode(&arguments),
+            "pisano" => pisano_mode(&arguments),
+            "lookup" => lookup_mode(&arguments),
+            "recurrence" => recurrence_mode(arguments),
+            "verify" => verify_mode(chosen_algorithm.unwrap_or(&algorithms::FastDoubling),
+                                    selection),
+            _ => Err(format!("Unknown mode '{}', available modes: batch, bench, binet, mod, \
+                              pisano, lookup, recurrence, verify", mode)),
+        };
+    }
+
+    if let Some(selection) = selection {
+        return match &integer_type {
+            Some(type_name) => {
+                let integer_type = checked::find_integer_type(type_name).ok_or(format!(
+                    "Unknown type '{}', available types: {}", type_name,
+                    checked::integer_type_names()))?;
+                (integer_type.print_terms)(&selection, integer_type.name, algorithm,
+                                           number_format)
+            }
+            None => sequence::print_terms::<BigInt>(&selection, "BigInt", algorithm,
+                                                    number_format),
+        };
+    }
+
+    let fibonacci_index = match index {
+        Some(index) => index,
+        None => {
+            println!("Welcome to nth Fibonacci Number program!");
+            println!("Please choose the index you want for the Fibonacci number calculation.");
+            read_input_line()
+        }
+    };
+    let fibonacci_index = parse_signed_index(&fibonacci_index)?;
+
+    let results = fibonacci_result(algorithm, integer_type.as_deref(), fibonacci_index)?;
+
+    match number_format {
+        format::NumberFormat::DigitCount => println!("The Fibonacci number for index {} has {} \
+            digits!", fibonacci_index, format::format_number(&results, number_format)),
+        _ => println!("The Fibonacci number for index {} is {}!", fibonacci_index,
+                      format::format_number(&results, number_format)),
+    }
+
+    let digits = format::decimal_digits(results.magnitude());
+    if number_format == format::NumberFormat::Auto && digits > format::MAX_AUTO_DIGITS {
+        println!("It has {} digits, use --format decimal to print all of them.", digits);
+    }
+    println!("{}", type_hint(fibonacci_index));
+    Ok(())
+}
+
+fn main() {
+    if let Err(error) = run(env::args().skip(1).collect()) {
+        eprintln!("{}", error);
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::BigUint;
+
+    #[test]
+    fn matches_the_original_table(){
+        //First 20 fibonacci number (Including the 0 index one)
+        let fibonacci_number_list : [u32; 21] = [0, 1, 1, 2, 3, 5,
+            8, 13, 21, 34, 55, 89, 144, 233, 377, 610, 987, 1597, 2584, 4181, 6765];
+
+        for (index, expected) in fibonacci_number_list.iter().enumerate() {
+            assert_eq!(algorithms::fast_doubling(index), BigUint::from(*expected),
+                       "index = {}", index);
+        }
+    }
+
+    #[test]
+    fn large_indices(){
+        assert_eq!(algorithms::fast_doubling(100).to_string(), "354224848179261915075");
+        assert_eq!(algorithms::fast_doubling(1000).to_string().len(), 209);
+
+        //Every term is the sum of the previous two, even far past the range of any u128.
+        let index = 5000;
+        assert_eq!(algorithms::fast_doubling(index),
+                   algorithms::fast_doubling(index - 1) + algorithms::fast_doubling(index - 2));
+    }
+
+    #[test]
+    fn negative_indices(){
+        //What the prompt reads, and what --index, --from and --to are given.
+        assert_eq!(parse_signed_index("-6\n"), Ok(-6));
+        assert_eq!(parse_signed_index(" 7 "), Ok(7));
+        assert!(parse_signed_index("-").is_err());
+
+        let signs : Vec<String> = (-6..=0)
+            .map(|index| fibonacci_number(&algorithms::FastDoubling, index).unwrap().to_string())
+            .collect();
+        assert_eq!(signs, ["-8", "5", "-3", "2", "-1", "1", "0"]);
+
+        for algorithm in algorithms::ALGORITHMS {
+            assert_eq!(fibonacci_number(algorithm, -20), Ok(BigInt::from(-6765)), "{}",
+                       algorithm.name());
+        }
+        assert!(fibonacci_number(&algorithms::NaiveRecursive, -41).is_err());
+
+        assert_eq!(fibonacci_number_of_type("i16", -20), Ok(String::from("-6765")));
+        assert_eq!(fibonacci_number_of_type("u16", -19), Ok(String::from("4181")));
+        assert!(fibonacci_number_of_type("u16", -20).is_err());
+        assert_eq!(type_hint(-20), "It fits in i16, no unsigned type can hold a negative number.");
+
+        let mut arguments : Vec<String> = ["--from", "-5", "--to", "3"].iter()
+            .map(|argument| argument.to_string()).collect();
+        assert!(matches!(take_selection(&mut arguments),
+                         Ok(Some(sequence::Selection::Range(-5, 3)))));
     }
 }
@@ -0,0 +1,151 @@
+use num_bigint::{BigInt, BigUint, Sign};
+
+//Fibonacci numbers modulo m, and Pisano periods.
+//Note - The same fast doubling as the big integer version, but every value is kept below m, so
+//          even an index with thousands of digits only takes a few thousand cheap steps.
+//       Products of two values below m < 2^64 always fit in a u128.
+//       The Pisano period of m is the length of the cycle F(n) mod m repeats with, it always
+//          starts again with 0, 1 and is never more than 6m long.
+
+//Fast doubling over the bits of the index, most significant bit first.
+fn fib_mod_bits(bits : impl Iterator<Item = bool>, modulus : u64) -> u64{
+    let modulus = modulus as u128;
+    //(F(k), F(k + 1)) mod m, starting from k = 0.
+    let mut current : u128 = 0;
+    let mut next : u128 = 1 % modulus;
+
+    for bit in bits {
+        let doubled = current * ((2 * next + modulus - current) % modulus) % modulus;
+        let doubled_next = (current * current % modulus + next * next % modulus) % modulus;
+
+        if bit {
+            next = (doubled + doubled_next) % modulus;
+            current = doubled_next;
+        } else {
+            current = doubled;
+            next = doubled_next;
+        }
+    }
+
+    current as u64
+}
+
+//F(index) mod modulus, modulus has to be at least 1.
+pub fn fib_mod(index : u128, modulus : u64) -> u64{
+    let bits = (0..u128::BITS - index.leading_zeros()).rev().map(|bit| (index >> bit) & 1 == 1);
+    fib_mod_bits(bits, modulus)
+}
+
+pub fn fib_mod_big(index : &BigUint, modulus : u64) -> u64{
+    fib_mod_bits((0..index.bits()).rev().map(|bit| index.bit(bit)), modulus)
+}
+
+//Same as fib_mod, for an index written in decimal that can be any number of digits long, or
+//negative, where F(-n) = -F(n) for even n.
+pub fn fib_mod_decimal(index : &str, modulus : u64) -> Result<u64, String>{
+    if modulus == 0 {
+        return Err(String::from("The modulus has to be at least 1!"));
+    }
+
+    let index : BigInt = index.trim().parse()
+        .map_err(|_| format!("'{}' is not a valid index!", index.trim()))?;
+    let residue = fib_mod_big(index.magnitude(), modulus);
+
+    if index.sign() == Sign::Minus && !index.magnitude().bit(0) && residue != 0 {
+        Ok(modulus - residue)
+    } else {
+        Ok(residue)
+    }
+}
+
+//Largest modulus pisano_period walks, up to 6 x 10^7 steps or well under a second.
+pub const MAX_PISANO_MODULUS : u64 = 10_000_000;
+
+//Length of the cycle of F(n) mod modulus, found by walking the sequence until 0, 1 comes back.
+//Note - This takes O(m) steps, up to 6m, hence the limit on the modulus.
+pub fn pisano_period(modulus : u64) -> Result<u64, String>{
+    if modulus == 0 || modulus > MAX_PISANO_MODULUS {
+        return Err(format!("Please keep the modulus between 1 and {}!", MAX_PISANO_MODULUS));
+    }
+
+    let modulus = modulus as u128;
+    let start = (0, 1 % modulus);
+    let mut state = start;
+    let mut period : u64 = 0;
+
+    loop {
+        state = (state.1, (state.0 + state.1) % modulus);
+        period += 1;
+
+        if state == start {
+            return Ok(period);
+        }
+    }
+}
+
+//The residues F(0) mod m, ..., F(period - 1) mod m that keep repeating.
+pub fn pisano_residues(modulus : u64, count : u64) -> Vec<u64>{
+    let mut residues = Vec::new();
+    let (mut current, mut next) = (0u128, 1 % modulus as u128);
+
+    for _ in 0..count {
+        residues.push(current as u64);
+        (current, next) = (next, (current + next) % modulus as u128);
+    }
+
+    residues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms;
+
+    #[test]
+    fn matches_big_integer_remainders(){
+        for modulus in [1, 2, 3, 7, 10, 1_000_000_007, u64::MAX] {
+            for index in 0..300 {
+                let expected = algorithms::fast_doubling(index) % BigUint::from(modulus);
+                assert_eq!(BigUint::from(fib_mod(index as u128, modulus)), expected,
+                           "F({}) mod {}", index, modulus);
+            }
+        }
+    }
+
+    #[test]
+    fn huge_indices_agree(){
+        let index = u128::MAX;
+        assert_eq!(fib_mod_decimal(&index.to_string(), 1_000_000_007),
+                   Ok(fib_mod(index, 1_000_000_007)));
+
+        //F(n) mod 10 repeats every 60 terms and 10^30 + 7 = 47 (mod 60), so this is F(47) mod 10.
+        let index = format!("1{}7", "0".repeat(29));
+        assert_eq!(fib_mod_decimal(&index, 10), Ok(3));
+    }
+
+    #[test]
+    fn negative_indices(){
+        //F(-6) = -8, F(-7) = 13
+        assert_eq!(fib_mod_decimal("-6", 10), Ok(2));
+        assert_eq!(fib_mod_decimal("-7", 10), Ok(3));
+        assert_eq!(fib_mod_decimal("-6", 8), Ok(0));
+        assert_eq!(fib_mod_decimal("-6", 1), Ok(0));
+    }
+
+    #[test]
+    fn known_pisano_periods(){
+        let periods = [(1, 1), (2, 3), (3, 8), (4, 6), (5, 20), (10, 60), (100, 300), (1000, 1500)];
+
+        for (modulus, period) in periods {
+            assert_eq!(pisano_period(modulus), Ok(period), "modulus {}", modulus);
+        }
+
+        //10^7 = 2^7 x 5^7, lcm(3 x 2^6, 20 x 5^6) = 1.5 x 10^7.
+        assert_eq!(pisano_period(MAX_PISANO_MODULUS), Ok(15_000_000));
+        assert!(pisano_period(MAX_PISANO_MODULUS + 1).is_err());
+        assert!(pisano_period(1_000_000_000_000).is_err());
+        assert!(pisano_period(0).is_err());
+
+        assert_eq!(pisano_residues(3, 8), [0, 1, 1, 2, 0, 2, 2, 1]);
+    }
+}
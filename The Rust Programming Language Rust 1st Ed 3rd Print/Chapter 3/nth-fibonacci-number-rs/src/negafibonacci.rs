@@ -0,0 +1,41 @@
+use num_bigint::{BigInt, BigUint, Sign};
+
+//The sequence extended to negative indices by running the recurrence backwards,
+//F(n - 2) = F(n) - F(n - 1), which gives ..., F(-4) = -3, F(-3) = 2, F(-2) = -1, F(-1) = 1.
+//Note - These are the same numbers with alternating signs, F(-n) = (-1)^(n + 1) * F(n), so only
+//          the even negative indices give negative numbers.
+
+//Whether F(index) is negative.
+pub fn is_negative_term(index : isize) -> bool{
+    index < 0 && index % 2 == 0
+}
+
+//F(index) from F(|index|).
+pub fn with_sign(index : isize, magnitude : BigUint) -> BigInt{
+    let sign = if is_negative_term(index) { Sign::Minus } else { Sign::Plus };
+    BigInt::from_biguint(sign, magnitude)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms;
+
+    fn signed_fibonacci(index : isize) -> BigInt{
+        with_sign(index, algorithms::fast_doubling(index.unsigned_abs()))
+    }
+
+    #[test]
+    fn signs_alternate_below_zero(){
+        let terms : Vec<BigInt> = (-8..=0).map(signed_fibonacci).collect();
+        let expected : Vec<BigInt> = [-21, 13, -8, 5, -3, 2, -1, 1, 0].iter()
+            .map(|term| BigInt::from(*term)).collect();
+        assert_eq!(terms, expected);
+
+        //The recurrence holds across zero as well.
+        for index in -100..=100 {
+            assert_eq!(signed_fibonacci(index + 2),
+                       signed_fibonacci(index + 1) + signed_fibonacci(index), "index {}", index);
+        }
+    }
+}
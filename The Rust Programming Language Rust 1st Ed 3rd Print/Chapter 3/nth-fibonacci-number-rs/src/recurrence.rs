@@ -0,0 +1,170 @@
+use num_bigint::BigInt;
+use num_traits::{One, Zero};
+
+//Linear recurrences of order k, a(n) = c1 * a(n - 1) + c2 * a(n - 2) + ... + ck * a(n - k),
+//starting from the seeds a(0), ..., a(k - 1).
+//Note - The Fibonacci sequence is the one with coefficients 1, 1 and seeds 0, 1.
+//       Large indices use the companion matrix, for Fibonacci that is the usual | 1 1 | matrix:
+//                                                                                | 1 0 |
+//          | c1 c2 ... ck |   | a(n + k - 1) |   | a(n + k)     |
+//          | 1  0  ... 0  |   | ...          |   | a(n + k - 1) |
+//          | ...          | x | a(n + 1)     | = | ...          |
+//          | 0  ... 1  0  |   | a(n)         |   | a(n + 1)     |
+//       so a(n) is the last entry of M^n applied to the seeds, and M^n takes about 2 * log2(n)
+//          matrix products by repeated squaring.
+
+pub struct LinearRecurrence {
+    pub name : String,
+    pub coefficients : Vec<BigInt>,
+    pub seeds : Vec<BigInt>,
+}
+
+type Matrix = Vec<Vec<BigInt>>;
+
+fn identity(size : usize) -> Matrix{
+    (0..size).map(|row| (0..size)
+        .map(|column| if row == column { BigInt::one() } else { BigInt::zero() }).collect())
+        .collect()
+}
+
+fn multiply(left : &Matrix, right : &Matrix) -> Matrix{
+    let size = left.len();
+
+    (0..size).map(|row| (0..size)
+        .map(|column| (0..size).map(|middle| &left[row][middle] * &right[middle][column]).sum())
+        .collect())
+        .collect()
+}
+
+fn to_integers(values : &[i64]) -> Vec<BigInt>{
+    values.iter().map(|value| BigInt::from(*value)).collect()
+}
+
+pub const PRESET_NAMES : &str = "fibonacci, lucas, pell, tribonacci, k-bonacci (e.g. 4-bonacci)";
+
+impl LinearRecurrence {
+    pub fn new(name : &str, coefficients : Vec<BigInt>, seeds : Vec<BigInt>)
+               -> Result<LinearRecurrence, String>{
+        if coefficients.is_empty() {
+            return Err(String::from("A recurrence needs at least one coefficient!"));
+        }
+
+        if coefficients.len() != seeds.len() {
+            return Err(format!("A recurrence of order {} needs {} seeds, but {} were given!",
+                               coefficients.len(), coefficients.len(), seeds.len()));
+        }
+
+        Ok(LinearRecurrence { name : name.to_string(), coefficients, seeds })
+    }
+
+    //k-bonacci adds up the previous k terms, starting from k - 1 zeros and a one.
+    pub fn k_bonacci(order : usize) -> Result<LinearRecurrence, String>{
+        let mut seeds = vec![0; order.saturating_sub(1)];
+        seeds.push(1);
+
+        LinearRecurrence::new(&format!("{}-bonacci", order), to_integers(&vec![1; order]),
+                              to_integers(&seeds))
+    }
+
+    pub fn preset(name : &str) -> Option<LinearRecurrence>{
+        let name = name.trim().to_lowercase();
+
+        let (coefficients, seeds) : (&[i64], &[i64]) = match name.as_str() {
+            "fibonacci" => (&[1, 1], &[0, 1]),
+            "lucas" => (&[1, 1], &[2, 1]),
+            "pell" => (&[2, 1], &[0, 1]),
+            "tribonacci" => (&[1, 1, 1], &[0, 0, 1]),
+            _ => {
+                let order = name.strip_suffix("-bonacci")?.parse().ok()?;
+                return LinearRecurrence::k_bonacci(order).ok();
+            }
+        };
+
+        LinearRecurrence::new(&name, to_integers(coefficients), to_integers(seeds)).ok()
+    }
+
+    pub fn order(&self) -> usize{
+        self.coefficients.len()
+    }
+
+    //a(0), a(1), ..., one term at a time, straight from the definition.
+    pub fn terms(&self, count : usize) -> Vec<BigInt>{
+        let mut terms : Vec<BigInt> = self.seeds.iter().take(count).cloned().collect();
+
+        while terms.len() < count {
+            let next = self.coefficients.iter().zip(terms.iter().rev())
+                .map(|(coefficient, term)| coefficient * term)
+                .sum();
+            terms.push(next);
+        }
+
+        terms
+    }
+
+    //a(index) with the companion matrix.
+    pub fn nth(&self, index : usize) -> BigInt{
+        let order = self.order();
+
+        let mut companion = vec![vec![BigInt::zero(); order]; order];
+        companion[0] = self.coefficients.clone();
+        for row in 1..order {
+            companion[row][row - 1] = BigInt::one();
+        }
+
+        let mut power = identity(order);
+        let mut exponent = index;
+
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                power = multiply(&power, &companion);
+            }
+
+            companion = multiply(&companion, &companion);
+            exponent >>= 1;
+        }
+
+        //The seeds are the starting state, a(k - 1) first and a(0) last.
+        power[order - 1].iter().zip(self.seeds.iter().rev())
+            .map(|(entry, seed)| entry * seed)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms;
+
+    #[test]
+    fn matrix_matches_the_definition(){
+        for name in ["fibonacci", "lucas", "pell", "tribonacci", "5-bonacci", "1-bonacci"] {
+            let recurrence = LinearRecurrence::preset(name).unwrap();
+
+            for (index, term) in recurrence.terms(60).iter().enumerate() {
+                assert_eq!(recurrence.nth(index), *term, "{} at {}", name, index);
+            }
+        }
+
+        let custom = LinearRecurrence::new("custom", to_integers(&[3, -2, 0, 1]),
+                                           to_integers(&[-1, 4, 0, 7])).unwrap();
+        for (index, term) in custom.terms(40).iter().enumerate() {
+            assert_eq!(custom.nth(index), *term);
+        }
+    }
+
+    #[test]
+    fn known_sequences(){
+        let first_terms = |name : &str| -> Vec<i64> {
+            LinearRecurrence::preset(name).unwrap().terms(10).iter()
+                .map(|term| term.to_string().parse().unwrap()).collect()
+        };
+
+        assert_eq!(first_terms("lucas"), [2, 1, 3, 4, 7, 11, 18, 29, 47, 76]);
+        assert_eq!(first_terms("pell"), [0, 1, 2, 5, 12, 29, 70, 169, 408, 985]);
+        assert_eq!(first_terms("tribonacci"), [0, 0, 1, 1, 2, 4, 7, 13, 24, 44]);
+        assert_eq!(first_terms("4-bonacci"), [0, 0, 0, 1, 1, 2, 4, 8, 15, 29]);
+
+        let fibonacci = LinearRecurrence::preset("fibonacci").unwrap();
+        assert_eq!(fibonacci.nth(5000), BigInt::from(algorithms::fast_doubling(5000)));
+    }
+}
@@ -0,0 +1,198 @@
+use std::fmt::Display;
+use std::iter::FusedIterator;
+
+use num_bigint::{BigInt, BigUint, ToBigUint};
+use num_traits::{CheckedAdd, CheckedSub, One, Zero};
+
+use crate::algorithms::{self, FibonacciAlgorithm};
+use crate::format::{self, NumberFormat};
+use crate::negafibonacci;
+
+//Lazy Fibonacci sequence, F(0), F(1), F(2), ...
+//Note - Works with any integer type that has checked addition, which includes every primitive
+//          integer type as well as BigUint.
+//       For fixed width types the sequence simply ends after the last term that fits, so
+//          Fibonacci::<u8>::new() yields 14 terms, F(0) to F(13) = 233.
+//       A range far out starts with two terms from the chosen algorithm instead of adding its way
+//          up from F(0), --from 300000 is two fast doubling calls and not 300000 additions.
+pub struct Fibonacci<T> {
+    current : Option<T>,
+    //None once F(k + 1) overflowed, which only matters when it is time to yield it.
+    next : Option<T>,
+}
+
+impl<T : Zero + One + CheckedAdd> Fibonacci<T> {
+    pub fn new() -> Fibonacci<T>{
+        Fibonacci { current : Some(T::zero()), next : Some(T::one()) }
+    }
+}
+
+impl<T : Zero + One + CheckedAdd + TryFrom<BigUint>> Fibonacci<T> {
+    //F(index), F(index + 1), ..., None when F(index) does not fit in T.
+    pub fn starting_at(index : usize, algorithm : &dyn FibonacciAlgorithm)
+                       -> Option<Fibonacci<T>>{
+        let current = T::try_from(algorithm.fibonacci(index)).ok()?;
+        let next = T::try_from(algorithm.fibonacci(index + 1)).ok();
+
+        Some(Fibonacci { current : Some(current), next })
+    }
+}
+
+impl<T : Zero + One + CheckedAdd> Default for Fibonacci<T> {
+    fn default() -> Fibonacci<T>{
+        Fibonacci::new()
+    }
+}
+
+impl<T : CheckedAdd> Iterator for Fibonacci<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T>{
+        let value = self.current.take()?;
+        self.current = self.next.take();
+
+        if let Some(current) = &self.current {
+            self.next = value.checked_add(current);
+        }
+
+        Some(value)
+    }
+}
+
+impl<T : CheckedAdd> FusedIterator for Fibonacci<T> {}
+
+//Which terms the sequence modes print.
+pub enum Selection {
+    //--from A --to B, both inclusive, A can be negative.
+    Range(isize, isize),
+    //--first N
+    First(usize),
+    //--below BOUND, every term strictly less than the bound.
+    Below(BigUint),
+}
+
+impl Selection {
+    //Index of the last term wanted, None when that depends on the values (--below).
+    fn last_index(&self) -> Option<usize>{
+        match self {
+            Selection::Range(_, to) => usize::try_from(*to).ok(),
+            Selection::First(count) => (*count).checked_sub(1),
+            Selection::Below(_) => None,
+        }
+    }
+}
+
+//Every printed term goes through format_number, so --format and --significant apply to ranges
+//too and a huge term is not dumped digit by digit.
+fn print_term(index : isize, term : impl Into<BigInt>, number_format : NumberFormat){
+    println!("F({}) = {}", index, format::format_number(&term.into(), number_format));
+}
+
+//Negative indices of a range, from the terms at the matching positive ones.
+fn print_negative_terms<T>(from : isize, to : isize, type_name : &str,
+                           algorithm : &dyn FibonacciAlgorithm, number_format : NumberFormat)
+                           -> Result<(), String>
+    where T : Zero + One + CheckedAdd + CheckedSub + Clone + Display + TryFrom<BigUint>
+              + Into<BigInt>{
+    //Magnitudes from the one closest to zero up to |from|, which is printed first.
+    let smallest = to.min(-1).unsigned_abs();
+    algorithms::check_index_limit(algorithm, smallest + 1)?;
+
+    let terms : Vec<T> = Fibonacci::starting_at(smallest, algorithm)
+        .map_or_else(Vec::new, |terms| terms.take(from.unsigned_abs() - smallest + 1).collect());
+
+    for index in from..=to.min(-1) {
+        let term = terms.get(index.unsigned_abs() - smallest)
+            .ok_or(format!("F({}) does not fit in {}.", index, type_name))?;
+
+        if negafibonacci::is_negative_term(index) {
+            let negative = T::zero().checked_sub(term).ok_or(format!(
+                "F({}) = -{} is negative, which does not fit in {}.", index, term, type_name))?;
+            print_term(index, negative, number_format);
+        } else {
+            print_term(index, term.clone(), number_format);
+        }
+    }
+
+    Ok(())
+}
+
+//Prints the selected terms in the integer type T, with an error if the type runs out of room
+//before the selection does.
+pub fn print_terms<T>(selection : &Selection, type_name : &str, algorithm : &dyn FibonacciAlgorithm,
+                      number_format : NumberFormat) -> Result<(), String>
+    where T : Zero + One + CheckedAdd + CheckedSub + Clone + Display + ToBigUint + TryFrom<BigUint>
+              + Into<BigInt>{
+    let first_index = match selection {
+        Selection::Range(from, to) if *from < 0 => {
+            print_negative_terms::<T>(*from, *to, type_name, algorithm, number_format)?;
+            0
+        }
+        Selection::Range(from, _) => from.unsigned_abs(),
+        _ => 0,
+    };
+    algorithms::check_index_limit(algorithm, first_index + 1)?;
+
+    let (start, terms) = match Fibonacci::<T>::starting_at(first_index, algorithm) {
+        Some(terms) => (first_index, terms),
+        //Past the end of T, counting up from F(0) finds the last term that fits.
+        None => (0, Fibonacci::new()),
+    };
+    let mut printed_up_to = None;
+
+    for (index, term) in (start..).zip(terms) {
+        if let Selection::Below(bound) = selection {
+            if term.to_biguint().is_some_and(|term| term >= *bound) {
+                return Ok(());
+            }
+        } else if selection.last_index().is_none_or(|last_index| index > last_index) {
+            return Ok(());
+        }
+
+        if index >= first_index {
+            print_term(index as isize, term, number_format);
+        }
+
+        printed_up_to = Some(index);
+    }
+
+    Err(format!("The sequence stops after F({}), the next term does not fit in {}.",
+                printed_up_to.unwrap_or_default(), type_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms;
+
+    #[test]
+    fn fixed_width_sequences_stop_on_overflow(){
+        let terms : Vec<u8> = Fibonacci::new().collect();
+        assert_eq!(terms.len(), 14);
+        assert_eq!(terms[13], 233);
+
+        assert_eq!(Fibonacci::<i64>::new().count(), 93);
+        assert_eq!(Fibonacci::<u128>::new().last(),
+                   Some(332_825_110_087_067_562_321_196_029_789_634_457_848));
+    }
+
+    #[test]
+    fn big_integer_sequence_keeps_going(){
+        let terms : Vec<BigUint> = Fibonacci::new().take(300).collect();
+
+        for (index, term) in terms.iter().enumerate() {
+            assert_eq!(*term, algorithms::fast_doubling(index));
+        }
+    }
+
+    #[test]
+    fn starting_further_out(){
+        let terms : Vec<BigUint> = Fibonacci::starting_at(10_000, &algorithms::FastDoubling)
+            .unwrap().take(3).collect();
+        assert_eq!(terms, [10_000, 10_001, 10_002].map(algorithms::fast_doubling));
+
+        let terms : Vec<u8> = Fibonacci::starting_at(12, &algorithms::Iterative).unwrap().collect();
+        assert_eq!(terms, [144, 233]);
+        assert!(Fibonacci::<u8>::starting_at(14, &algorithms::Iterative).is_none());
+    }
+}
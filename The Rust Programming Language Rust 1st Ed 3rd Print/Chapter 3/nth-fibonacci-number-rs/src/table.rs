@@ -0,0 +1,70 @@
+use num_bigint::BigUint;
+
+//Every Fibonacci number that fits in a u64 or a u128, worked out by the compiler.
+//Note - Overflow while evaluating a const is a compile error, so the tables building at all
+//          proves every term in them fits, and the lengths match checked::max_index + 1.
+//       const fn has no for loops, hence the while loop.
+
+macro_rules! fibonacci_table {
+    ($name:ident, $type:ty, $length:expr) => {
+        const fn $name() -> [$type; $length]{
+            let mut table : [$type; $length] = [0; $length];
+            table[1] = 1;
+
+            let mut index = 2;
+            while index < $length {
+                table[index] = table[index - 1] + table[index - 2];
+                index += 1;
+            }
+
+            table
+        }
+    };
+}
+
+fibonacci_table!(u64_table, u64, 94);
+fibonacci_table!(u128_table, u128, 187);
+
+//F(0) to F(93)
+pub const U64_TABLE : [u64; 94] = u64_table();
+//F(0) to F(186)
+pub const U128_TABLE : [u128; 187] = u128_table();
+
+//F(index) when it is in the tables, None past F(186).
+pub fn lookup(index : usize) -> Option<BigUint>{
+    U128_TABLE.get(index).map(|term| BigUint::from(*term))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::{self, FibonacciAlgorithm};
+    use crate::checked;
+
+    #[test]
+    fn tables_match_the_runtime_algorithms(){
+        assert_eq!(U64_TABLE.len(), checked::max_index::<u64>() + 1);
+        assert_eq!(U128_TABLE.len(), checked::max_index::<u128>() + 1);
+
+        for (index, term) in U64_TABLE.iter().enumerate() {
+            assert_eq!(BigUint::from(*term), algorithms::Iterative.fibonacci(index), "F({})",
+                       index);
+        }
+
+        for (index, term) in U128_TABLE.iter().enumerate() {
+            assert_eq!(BigUint::from(*term), algorithms::fast_doubling(index), "F({})", index);
+        }
+
+        //The checked iterator gets there by adding, without the tables.
+        for (index, term) in U64_TABLE.iter().enumerate() {
+            assert_eq!(checked::fib::<u64>(index), Ok(*term), "F({})", index);
+        }
+
+        for (index, term) in U128_TABLE.iter().enumerate() {
+            assert_eq!(checked::fib::<u128>(index), Ok(*term), "F({})", index);
+        }
+
+        assert_eq!(lookup(186), Some(algorithms::fast_doubling(186)));
+        assert_eq!(lookup(187), None);
+    }
+}
@@ -0,0 +1,153 @@
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Signed};
+
+//Classical identities checked against computed Fibonacci numbers, for any integer n and m:
+//      Cassini         F(n - 1) * F(n + 1) - F(n)^2 = (-1)^n
+//      Catalan         F(n)^2 - F(n - r) * F(n + r) = (-1)^(n - r) * F(r)^2
+//      d'Ocagne        F(m) * F(n + 1) - F(m + 1) * F(n) = (-1)^n * F(m - n)
+//      GCD             gcd(F(m), F(n)) = |F(gcd(m, n))|
+//      Sum             F(0) + F(1) + ... + F(n - 1) = F(n + 1) - 1, for n >= 0
+//Note - The terms all come from the algorithm being checked, so a mismatch means either the
+//          algorithm or the identity is wrong, and the identities have been around for a while.
+//       d'Ocagne and GCD check every pair in the range, so the range is kept fairly small.
+
+pub const DEFAULT_RANGE : (isize, isize) = (-100, 100);
+pub const MAX_INDEX : isize = 1_000;
+pub const MAX_RANGE : isize = 500;
+//Catalan is checked for r = 1 to MAX_CATALAN_R.
+pub const MAX_CATALAN_R : isize = 10;
+//Mismatches kept per identity, the rest are only counted.
+pub const MAX_REPORTED_MISMATCHES : usize = 10;
+
+pub struct IdentityResult {
+    pub name : &'static str,
+    pub checks : usize,
+    pub mismatches : usize,
+    pub reported : Vec<String>,
+}
+
+impl IdentityResult {
+    fn new(name : &'static str) -> IdentityResult{
+        IdentityResult { name, checks : 0, mismatches : 0, reported : Vec::new() }
+    }
+
+    fn check(&mut self, left : BigInt, right : BigInt, case : impl FnOnce() -> String){
+        self.checks += 1;
+
+        if left != right {
+            self.mismatches += 1;
+            if self.reported.len() < MAX_REPORTED_MISMATCHES {
+                self.reported.push(format!("{}: {} != {}", case(), left, right));
+            }
+        }
+    }
+}
+
+//F(low) to F(high), computed once.
+struct Terms {
+    low : isize,
+    terms : Vec<BigInt>,
+}
+
+impl Terms {
+    fn get(&self, index : isize) -> &BigInt{
+        &self.terms[(index - self.low) as usize]
+    }
+}
+
+//(-1)^exponent
+fn alternating(exponent : isize) -> BigInt{
+    if exponent.rem_euclid(2) == 0 { BigInt::one() } else { -BigInt::one() }
+}
+
+pub fn verify(from : isize, to : isize, fibonacci : impl Fn(isize) -> Result<BigInt, String>)
+              -> Result<Vec<IdentityResult>, String>{
+    if from > to {
+        return Err(format!("Invalid range, {} is past {}!", from, to));
+    }
+
+    if from < -MAX_INDEX || to > MAX_INDEX || to - from > MAX_RANGE {
+        return Err(format!("Please keep the range within -{} to {} and at most {} indices wide!",
+                           MAX_INDEX, MAX_INDEX, MAX_RANGE));
+    }
+
+    //Large enough for n ± r, m - n and gcd(m, n) with m and n anywhere in the range.
+    let span = from.abs().max(to.abs()) + (to - from) + MAX_CATALAN_R + 1;
+    let terms = Terms { low : -span, terms : (-span..=span).map(&fibonacci)
+        .collect::<Result<Vec<_>, _>>()? };
+    let f = |index : isize| terms.get(index);
+
+    let mut cassini = IdentityResult::new("Cassini");
+    let mut catalan = IdentityResult::new("Catalan");
+    let mut ocagne = IdentityResult::new("d'Ocagne");
+    let mut gcd = IdentityResult::new("GCD");
+    let mut sum = IdentityResult::new("Sum");
+
+    for n in from..=to {
+        cassini.check(f(n - 1) * f(n + 1) - f(n) * f(n), alternating(n),
+                      || format!("n = {}", n));
+
+        for r in 1..=MAX_CATALAN_R {
+            catalan.check(f(n) * f(n) - f(n - r) * f(n + r), alternating(n - r) * f(r) * f(r),
+                          || format!("n = {}, r = {}", n, r));
+        }
+
+        for m in from..=to {
+            ocagne.check(f(m) * f(n + 1) - f(m + 1) * f(n), alternating(n) * f(m - n),
+                         || format!("m = {}, n = {}", m, n));
+            gcd.check(f(m).gcd(f(n)), f(m.gcd(&n)).abs(), || format!("m = {}, n = {}", m, n));
+        }
+    }
+
+    let mut running_sum = BigInt::from(0);
+    for n in 0..=to {
+        if n >= from {
+            sum.check(running_sum.clone(), f(n + 1) - 1, || format!("n = {}", n));
+        }
+        running_sum += f(n);
+    }
+
+    Ok(vec![cassini, catalan, ocagne, gcd, sum])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms;
+    use crate::negafibonacci;
+
+    fn fast_doubling(index : isize) -> Result<BigInt, String>{
+        Ok(negafibonacci::with_sign(index, algorithms::fast_doubling(index.unsigned_abs())))
+    }
+
+    #[test]
+    fn identities_hold(){
+        for result in verify(-30, 30, fast_doubling).unwrap() {
+            assert!(result.checks > 0, "{}", result.name);
+            assert_eq!(result.mismatches, 0, "{}: {:?}", result.name, result.reported);
+        }
+
+        let sum = verify(0, 9, fast_doubling).unwrap().pop().unwrap();
+        assert_eq!(sum.checks, 10);
+    }
+
+    #[test]
+    fn wrong_terms_are_caught(){
+        //F(7) = 14 instead of 13
+        let wrong = |index| fast_doubling(index)
+            .map(|term| if index == 7 { term + 1 } else { term });
+
+        for result in verify(0, 20, wrong).unwrap() {
+            assert!(result.mismatches > 0, "{}", result.name);
+            assert!(result.reported.len() <= MAX_REPORTED_MISMATCHES);
+        }
+    }
+
+    #[test]
+    fn range_limits(){
+        assert!(verify(5, 4, fast_doubling).is_err());
+        assert!(verify(0, MAX_RANGE + 1, fast_doubling).is_err());
+        assert!(verify(-MAX_INDEX - 1, -MAX_INDEX, fast_doubling).is_err());
+    }
+}
@@ -0,0 +1,61 @@
+use std::fmt;
+use std::ops::{Add, Neg, Sub};
+
+use crate::scale::{self, TemperatureScale};
+
+//A difference between two temperatures, like "it got 10 °C warmer".
+//Note - Unlike a temperature it has no absolute zero, can be negative in any scale and converts
+//          without the offset of the scales (see TemperatureScale::convert_delta).
+//       Temperature - Temperature gives a TemperatureDelta and Temperature + TemperatureDelta
+//          gives a Temperature back, adding two temperatures does not compile.
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TemperatureDelta {
+    value : f64,
+    scale : TemperatureScale,
+}
+
+impl TemperatureDelta {
+    pub fn new(value : f64, scale : TemperatureScale) -> TemperatureDelta{
+        TemperatureDelta { value, scale }
+    }
+
+    pub fn value(self) -> f64{
+        self.value
+    }
+
+    pub fn to(self, scale : TemperatureScale) -> TemperatureDelta{
+        TemperatureDelta::new(self.scale.convert_delta(self.value, scale), scale)
+    }
+}
+
+impl fmt::Display for TemperatureDelta {
+    fn fmt(&self, formatter : &mut fmt::Formatter) -> fmt::Result{
+        write!(formatter, "{} {}", scale::rounded(self.value), self.scale.symbol())
+    }
+}
+
+//Both sides end up in the scale of the left one.
+impl Add for TemperatureDelta {
+    type Output = TemperatureDelta;
+
+    fn add(self, other : TemperatureDelta) -> TemperatureDelta{
+        TemperatureDelta::new(self.value + other.to(self.scale).value, self.scale)
+    }
+}
+
+impl Sub for TemperatureDelta {
+    type Output = TemperatureDelta;
+
+    fn sub(self, other : TemperatureDelta) -> TemperatureDelta{
+        self + -other
+    }
+}
+
+impl Neg for TemperatureDelta {
+    type Output = TemperatureDelta;
+
+    fn neg(self) -> TemperatureDelta{
+        TemperatureDelta::new(-self.value, self.scale)
+    }
+}
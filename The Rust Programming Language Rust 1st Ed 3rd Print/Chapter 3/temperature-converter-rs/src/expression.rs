@@ -0,0 +1,172 @@
+use std::fmt;
+
+use crate::scale::{self, TemperatureScale};
+
+//One line conversions, <value> [°]<scale> [to | in | -> [°]<scale>], for example 98.6F,
+//-40 °C to K, 300K in F or 25c.
+//Note - Without a target scale the value is converted to every other scale.
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Expression {
+    pub value : f64,
+    pub from : TemperatureScale,
+    pub to : Option<TemperatureScale>,
+}
+
+//Points at the part of the input that could not be understood, counted in characters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub input : String,
+    pub position : usize,
+    pub length : usize,
+    pub message : String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, formatter : &mut fmt::Formatter) -> fmt::Result{
+        writeln!(formatter, "{}", self.input)?;
+        write!(formatter, "{}{} {}", " ".repeat(self.position), "^".repeat(self.length.max(1)),
+               self.message)
+    }
+}
+
+struct Parser<'a> {
+    input : &'a str,
+    characters : Vec<char>,
+    position : usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, position : usize, length : usize, message : String) -> ParseError{
+        ParseError { input : self.input.to_string(), position, length, message }
+    }
+
+    fn skip_whitespace(&mut self){
+        self.take_while(char::is_whitespace);
+    }
+
+    //Characters from here on while they match, with where they started.
+    fn take_while(&mut self, accept : impl Fn(char) -> bool) -> (usize, String){
+        let start = self.position;
+
+        while self.characters.get(self.position).is_some_and(|character| accept(*character)) {
+            self.position += 1;
+        }
+
+        (start, self.characters[start..self.position].iter().collect())
+    }
+
+    fn number(&mut self) -> Result<f64, ParseError>{
+        self.skip_whitespace();
+        let (start, sign) = self.take_while(|character| character == '+' || character == '-');
+        let (_, digits) = self.take_while(|character| character.is_ascii_digit()
+            || character == '.');
+        let number = sign + &digits;
+
+        if number.is_empty() {
+            return Err(self.error(start, 1, String::from("expected a temperature value here")));
+        }
+
+        number.parse().map_err(|_| self.error(start, number.chars().count(),
+                                              format!("'{}' is not a number", number)))
+    }
+
+    fn scale(&mut self, expected : &str) -> Result<TemperatureScale, ParseError>{
+        self.skip_whitespace();
+        if self.characters.get(self.position) == Some(&'°') {
+            self.position += 1;
+        }
+
+        let (start, name) = self.take_while(char::is_alphabetic);
+
+        if name.is_empty() {
+            return Err(self.error(start, 1, format!("expected {} here ({})", expected,
+                                                    scale::scale_names())));
+        }
+
+        scale::find_scale(&name).ok_or_else(|| self.error(start, name.chars().count(),
+            format!("unknown scale '{}', available scales: {}", name, scale::scale_names())))
+    }
+
+    //to, in or ->, or nothing at all at the end of the input.
+    fn separator(&mut self) -> Result<bool, ParseError>{
+        self.skip_whitespace();
+        if self.position == self.characters.len() {
+            return Ok(false);
+        }
+
+        let (start, word) = self.take_while(|character| !character.is_whitespace()
+            && character != '°');
+
+        match word.to_lowercase().as_str() {
+            "to" | "in" | "->" => Ok(true),
+            _ => Err(self.error(start, word.chars().count().max(1),
+                                String::from("expected 'to', 'in' or '->' here"))),
+        }
+    }
+
+    fn end(&mut self) -> Result<(), ParseError>{
+        self.skip_whitespace();
+        if self.position == self.characters.len() {
+            return Ok(());
+        }
+
+        Err(self.error(self.position, self.characters.len() - self.position,
+                       String::from("unexpected text after the conversion")))
+    }
+}
+
+pub fn parse_expression(input : &str) -> Result<Expression, ParseError>{
+    let input = input.trim_end();
+    let mut parser = Parser { input, characters : input.chars().collect(), position : 0 };
+
+    let value = parser.number()?;
+    let from = parser.scale("a scale")?;
+    let to = if parser.separator()? {
+        Some(parser.scale("the scale to convert to")?)
+    } else {
+        None
+    };
+    parser.end()?;
+
+    Ok(Expression { value, from, to })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples(){
+        let expression = |value, from, to| Ok(Expression { value, from, to });
+
+        assert_eq!(parse_expression("98.6F"), expression(98.6, TemperatureScale::Fahrenheit, None));
+        assert_eq!(parse_expression("-40 °C to K\n"), expression(-40.0, TemperatureScale::Celsius,
+                                                                 Some(TemperatureScale::Kelvin)));
+        assert_eq!(parse_expression("300K in F"), expression(300.0, TemperatureScale::Kelvin,
+                                                             Some(TemperatureScale::Fahrenheit)));
+        assert_eq!(parse_expression("25c"), expression(25.0, TemperatureScale::Celsius, None));
+        assert_eq!(parse_expression("  +1.5 Rankine -> °Ré"), expression(1.5,
+            TemperatureScale::Rankine, Some(TemperatureScale::Reaumur)));
+    }
+
+    #[test]
+    fn errors_point_at_the_problem(){
+        let error = |input : &str| {
+            let error = parse_expression(input).unwrap_err();
+            (error.position, error.length)
+        };
+
+        assert_eq!(error("F"), (0, 1));
+        assert_eq!(error("1.2.3 C"), (0, 5));
+        assert_eq!(error("98.6"), (4, 1));
+        assert_eq!(error("98.6 X to K"), (5, 1));
+        assert_eq!(error("-40 °C into K"), (7, 4));
+        assert_eq!(error("-40 °C to"), (9, 1));
+        assert_eq!(error("-40 °C to Kelvins"), (10, 7));
+        assert_eq!(error("25c please"), (4, 6));
+
+        assert_eq!(parse_expression("98.6 X to K").unwrap_err().to_string(), format!(
+            "98.6 X to K\n     ^ unknown scale 'X', available scales: {}", scale::scale_names()));
+    }
+}
@@ -1,45 +1,200 @@
+use std::env;
 use std::io;
+use std::process;
 
-fn celsius_to_fahrenheit(celsius : f32) -> f32{
-    (celsius * 1.8) + 32.0
+use delta::TemperatureDelta;
+use expression::Expression;
+use scale::TemperatureScale;
+use temperature::Temperature;
+
+mod delta;
+mod expression;
+mod scale;
+mod table;
+mod temperature;
+
+fn read_input_line() -> String{
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)
+        .expect("Failed to read line, please try again!");
+    input
 }
 
-fn fahrenheit_to_celsius(fahrenheit : f32) -> f32{
-    (fahrenheit - 32.0) * 5.0 / 9.0
+//Removes `--name` from the arguments wherever it is, returning whether it was given.
+fn take_flag(arguments : &mut Vec<String>, name : &str) -> bool{
+    match arguments.iter().position(|argument| argument == name) {
+        Some(index) => {
+            arguments.remove(index);
+            true
+        }
+        None => false,
+    }
 }
 
-fn main() {
-    println!("Welcome to temperature conversion program!");
-    println!("Please enter if you want to input Celsius or Fahrenheit.");
-    println!("Celcius / Celsius -> Convert Celsius to Fahrenheit");
-    println!("Fahrenheit -> Convert Fahrenheit to Celsius");
+//Removes `--name value` from the arguments wherever it is, returning the value if it was given.
+fn take_option(arguments : &mut Vec<String>, name : &str) -> Result<Option<String>, String>{
+    match arguments.iter().position(|argument| argument == name) {
+        None => Ok(None),
+        Some(index) if index + 1 < arguments.len() => {
+            let value = arguments.remove(index + 1);
+            arguments.remove(index);
+            Ok(Some(value))
+        }
+        Some(_) => Err(format!("Missing value for {}", name)),
+    }
+}
 
-    let mut temperature_selection : String = String::new();
-    io::stdin().read_line(&mut temperature_selection)
-        .expect("Failed to read line, please try again!");
-    temperature_selection = temperature_selection.to_lowercase();
-    let temperature_selection: String = temperature_selection.trim().parse()
-        .expect("Not a string, please try again!");
-    println!("Please enter the temperature value.");
+fn parse_number(value : &str, name : &str) -> Result<f64, String>{
+    value.trim().parse().map_err(|_| format!("'{}' is not a valid {}!", value.trim(), name))
+}
 
-    let mut temperature_value = String::new();
-    io::stdin().read_line(&mut temperature_value)
-        .expect("Failed to read line, please try again!");
-    let temperature_value: f32 = temperature_value.trim().parse()
-        .expect("Not a number, please try again!");
+fn select_scale(name : &str) -> Result<TemperatureScale, String>{
+    scale::find_scale(name).ok_or(format!("Unknown scale '{}', available scales: {}", name.trim(),
+                                          scale::scale_names()))
+}
+
+//Usage: temperature-converter-rs table --from A --to B [--step S] [--scale C]
+//           [--scales F,K,...] [--output text | markdown | csv] [--decimals N] [--delta]
+fn table_mode(mut arguments : Vec<String>, delta : bool) -> Result<(), String>{
+    let from = take_option(&mut arguments, "--from")?.ok_or("Missing --from, the first value!")?;
+    let to = take_option(&mut arguments, "--to")?.ok_or("Missing --to, the last value!")?;
+    let step = take_option(&mut arguments, "--step")?;
+    let from_scale = match take_option(&mut arguments, "--scale")? {
+        Some(name) => select_scale(&name)?,
+        None => TemperatureScale::Celsius,
+    };
+    let scales = match take_option(&mut arguments, "--scales")? {
+        Some(names) => names.split(',').map(select_scale).collect::<Result<Vec<_>, _>>()?,
+        None => scale::SCALES.to_vec(),
+    };
+    let output = match take_option(&mut arguments, "--output")? {
+        Some(name) => table::parse_table_format(&name)?,
+        None => table::TableFormat::Text,
+    };
+    let decimals = match take_option(&mut arguments, "--decimals")? {
+        Some(decimals) => table::parse_decimals(&decimals)?,
+        None => table::DEFAULT_DECIMALS,
+    };
+
+    if !arguments.is_empty() {
+        return Err(format!("Unexpected '{}', usage: table --from A --to B [--step S] [--scale C] \
+            [--scales F,K,...] [--output text | markdown | csv] [--decimals N] [--delta]",
+                           arguments.join(" ")));
+    }
+
+    let values = table::range_values(parse_number(&from, "value")?, parse_number(&to, "value")?,
+        step.map_or(Ok(1.0), |step| parse_number(&step, "step"))?)?;
+
+    //The scale the range is in comes first.
+    let mut columns = vec![from_scale];
+    for scale in scales {
+        if !columns.contains(&scale) {
+            columns.push(scale);
+        }
+    }
+
+    let mut rows = Vec::new();
+    for value in values {
+        let row = if delta {
+            let difference = TemperatureDelta::new(value, from_scale);
+            columns.iter().map(|scale| table::fixed(difference.to(*scale).value(), decimals))
+                .collect()
+        } else {
+            let temperature = Temperature::new(value, from_scale)
+                .map_err(|error| format!("{}, please keep the table above it!", error))?;
+            columns.iter().map(|scale| table::fixed(temperature.to(*scale).value(), decimals))
+                .collect()
+        };
+        rows.push(row);
+    }
+
+    let headers : Vec<String> = columns.iter().map(|scale| scale.symbol().to_string()).collect();
+    println!("{}", table::render(&headers, &rows, output));
+    Ok(())
+}
 
-    println!("The inputted string is {}", temperature_selection);
+//The conversion on one line, with a temperature that can exist unless it is a difference.
+fn parse_conversion(input : &str, delta : bool) -> Result<Expression, String>{
+    let expression = expression::parse_expression(input)
+        .map_err(|error| format!("Could not understand the temperature:\n{}", error))?;
 
-    if temperature_selection == "celsius" || temperature_selection == "celcius"{
-        let result = celsius_to_fahrenheit(temperature_value);
-        println!("The converted value is {} Fahrenheit.", result);
+    if !delta {
+        Temperature::new(expression.value, expression.from).map_err(|error| error.to_string())?;
+    }
+
+    Ok(expression)
+}
+
+//Keeps asking until a conversion is understood, None when the input ends (EOF).
+fn prompt_conversion(delta : bool) -> Option<Expression>{
+    println!("Welcome to temperature conversion program!");
+    if delta {
+        println!("Please enter the temperature difference to convert, e.g. 10C to F.");
+    } else {
+        println!("Please enter the temperature to convert, e.g. 98.6F, -40 °C to K or 300K in F.");
+    }
+    println!("Available scales: {}", scale::scale_names());
+
+    loop {
+        let input = read_input_line();
+        if input.is_empty() {
+            println!("No temperature entered, goodbye!");
+            return None;
+        }
+
+        match parse_conversion(&input, delta) {
+            Ok(expression) => return Some(expression),
+            Err(error) => println!("{}\nPlease try again!", error),
+        }
+    }
+}
 
-    } else if temperature_selection == "fahrenheit"{
-        let result = fahrenheit_to_celsius(temperature_value);
-        println!("The converted value is {} Celsius.", result);
+//Usage: temperature-converter-rs [--delta] [<value> <scale> [to <scale>]]
+//       temperature-converter-rs table ...
+fn run(mut arguments : Vec<String>) -> Result<(), String>{
+    let delta = take_flag(&mut arguments, "--delta");
 
+    if arguments.first().map(String::as_str) == Some("table") {
+        return table_mode(arguments.split_off(1), delta);
+    }
+
+    let expression = if arguments.is_empty() {
+        match prompt_conversion(delta) {
+            Some(expression) => expression,
+            None => return Ok(()),
+        }
     } else {
-        println!("Invalid entry, please try again!");
+        parse_conversion(&arguments.join(" "), delta)?
+    };
+
+    let targets = match expression.to {
+        Some(to) => vec![to],
+        None => scale::SCALES.iter().copied().filter(|scale| *scale != expression.from)
+            .collect(),
+    };
+
+    if delta {
+        let difference = TemperatureDelta::new(expression.value, expression.from);
+        for target in targets {
+            println!("A difference of {} is a difference of {} ({}).", difference,
+                     difference.to(target), target.name());
+        }
+        return Ok(());
+    }
+
+    let temperature = Temperature::new(expression.value, expression.from)
+        .map_err(|error| error.to_string())?;
 
+    for target in targets {
+        println!("{} is {} ({}).", temperature, temperature.to(target), target.name());
+    }
+
+    Ok(())
+}
+
+fn main() {
+    if let Err(error) = run(env::args().skip(1).collect()) {
+        eprintln!("{}", error);
+        process::exit(1);
     }
 }
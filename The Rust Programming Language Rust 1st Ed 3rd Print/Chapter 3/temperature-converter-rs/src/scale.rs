@@ -0,0 +1,198 @@
+//Temperature scales, all converted through Celsius.
+//Note - Every scale only needs its two formulas to and from Celsius, any pair of scales is then
+//          one conversion to Celsius and one back out of it.
+//       Réaumur, Rømer, Newton and Delisle are historical, Delisle even counts downwards from the
+//          boiling point of water, so colder is a higher number.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemperatureScale {
+    Celsius,
+    Fahrenheit,
+    Kelvin,
+    Rankine,
+    Reaumur,
+    Romer,
+    Newton,
+    Delisle,
+}
+
+pub const SCALES : [TemperatureScale; 8] = [TemperatureScale::Celsius,
+    TemperatureScale::Fahrenheit, TemperatureScale::Kelvin, TemperatureScale::Rankine,
+    TemperatureScale::Reaumur, TemperatureScale::Romer, TemperatureScale::Newton,
+    TemperatureScale::Delisle];
+
+impl TemperatureScale {
+    pub fn name(self) -> &'static str{
+        match self {
+            TemperatureScale::Celsius => "Celsius",
+            TemperatureScale::Fahrenheit => "Fahrenheit",
+            TemperatureScale::Kelvin => "Kelvin",
+            TemperatureScale::Rankine => "Rankine",
+            TemperatureScale::Reaumur => "Réaumur",
+            TemperatureScale::Romer => "Rømer",
+            TemperatureScale::Newton => "Newton",
+            TemperatureScale::Delisle => "Delisle",
+        }
+    }
+
+    pub fn symbol(self) -> &'static str{
+        match self {
+            TemperatureScale::Celsius => "°C",
+            TemperatureScale::Fahrenheit => "°F",
+            TemperatureScale::Kelvin => "K",
+            TemperatureScale::Rankine => "°R",
+            TemperatureScale::Reaumur => "°Ré",
+            TemperatureScale::Romer => "°Rø",
+            TemperatureScale::Newton => "°N",
+            TemperatureScale::Delisle => "°De",
+        }
+    }
+
+    //Other ways of writing the name, without accents and with the old celcius typo.
+    fn aliases(self) -> &'static [&'static str]{
+        match self {
+            TemperatureScale::Celsius => &["celcius"],
+            TemperatureScale::Rankine => &["ra"],
+            TemperatureScale::Reaumur => &["re", "reaumur"],
+            TemperatureScale::Romer => &["ro", "romer", "roemer"],
+            _ => &[],
+        }
+    }
+
+    pub fn to_celsius(self, value : f64) -> f64{
+        match self {
+            TemperatureScale::Celsius => value,
+            TemperatureScale::Fahrenheit => (value - 32.0) * 5.0 / 9.0,
+            TemperatureScale::Kelvin => value - 273.15,
+            TemperatureScale::Rankine => (value - 491.67) * 5.0 / 9.0,
+            TemperatureScale::Reaumur => value * 5.0 / 4.0,
+            TemperatureScale::Romer => (value - 7.5) * 40.0 / 21.0,
+            TemperatureScale::Newton => value * 100.0 / 33.0,
+            TemperatureScale::Delisle => 100.0 - value * 2.0 / 3.0,
+        }
+    }
+
+    pub fn celsius_to_scale(self, celsius : f64) -> f64{
+        match self {
+            TemperatureScale::Celsius => celsius,
+            TemperatureScale::Fahrenheit => (celsius * 1.8) + 32.0,
+            TemperatureScale::Kelvin => celsius + 273.15,
+            TemperatureScale::Rankine => (celsius + 273.15) * 1.8,
+            TemperatureScale::Reaumur => celsius * 4.0 / 5.0,
+            TemperatureScale::Romer => celsius * 21.0 / 40.0 + 7.5,
+            TemperatureScale::Newton => celsius * 33.0 / 100.0,
+            TemperatureScale::Delisle => (100.0 - celsius) * 1.5,
+        }
+    }
+
+    pub fn convert(self, value : f64, to : TemperatureScale) -> f64{
+        to.celsius_to_scale(self.to_celsius(value))
+    }
+
+    //Same conversion for a temperature difference, which leaves out the offset of each scale, so
+    //a change of 10 °C is a change of 18 °F and not 50 °F.
+    //Note - Every formula is linear, f(x) = a * x + b, and f(x) - f(0) = a * x drops the offset.
+    //       Delisle counts downwards, so warming up by 10 °C is a change of -15 °De.
+    pub fn convert_delta(self, delta : f64, to : TemperatureScale) -> f64{
+        let celsius = self.to_celsius(delta) - self.to_celsius(0.0);
+        to.celsius_to_scale(celsius) - to.celsius_to_scale(0.0)
+    }
+}
+
+pub fn find_scale(name : &str) -> Option<TemperatureScale>{
+    let name = name.trim().to_lowercase();
+
+    //The symbol works with or without the degree sign, °C or C.
+    SCALES.iter().copied().find(|scale| scale.name().to_lowercase() == name
+        || scale.symbol().to_lowercase().trim_start_matches('°') == name.trim_start_matches('°')
+        || scale.aliases().contains(&name.as_str()))
+}
+
+pub fn scale_names() -> String{
+    SCALES.iter().map(|scale| scale.name()).collect::<Vec<_>>().join(", ")
+}
+
+//Rounds away the floating point noise, so 98.6 °F shows up as 37 °C and not 36.99999999999999.
+pub fn rounded(value : f64) -> f64{
+    //Adding 0.0 turns -0 into 0.
+    (value * 10_000.0).round() / 10_000.0 + 0.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(left : f64, right : f64){
+        assert!((left - right).abs() < 1e-9, "{} != {}", left, right);
+    }
+
+    #[test]
+    fn fixed_points(){
+        //Absolute zero, freezing and boiling water in every scale.
+        let table = [
+            (TemperatureScale::Celsius, [-273.15, 0.0, 100.0]),
+            (TemperatureScale::Fahrenheit, [-459.67, 32.0, 212.0]),
+            (TemperatureScale::Kelvin, [0.0, 273.15, 373.15]),
+            (TemperatureScale::Rankine, [0.0, 491.67, 671.67]),
+            (TemperatureScale::Reaumur, [-218.52, 0.0, 80.0]),
+            (TemperatureScale::Romer, [-135.90375, 7.5, 60.0]),
+            (TemperatureScale::Newton, [-90.1395, 0.0, 33.0]),
+            (TemperatureScale::Delisle, [559.725, 150.0, 0.0]),
+        ];
+
+        for (from, from_values) in table.iter() {
+            for (to, to_values) in table.iter() {
+                for (from_value, to_value) in from_values.iter().zip(to_values.iter()) {
+                    assert_close(from.convert(*from_value, *to), *to_value);
+                }
+            }
+        }
+
+        assert_close(TemperatureScale::Celsius.convert(-40.0, TemperatureScale::Fahrenheit), -40.0);
+    }
+
+    #[test]
+    fn round_trips(){
+        for from in SCALES.iter() {
+            for to in SCALES.iter() {
+                for value in [-100.0, -1.5, 0.0, 36.6, 1000.0].iter() {
+                    assert_close(to.convert(from.convert(*value, *to), *from), *value);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn deltas_leave_out_the_offset(){
+        let celsius = TemperatureScale::Celsius;
+
+        assert_close(celsius.convert_delta(10.0, TemperatureScale::Fahrenheit), 18.0);
+        assert_close(celsius.convert_delta(10.0, TemperatureScale::Kelvin), 10.0);
+        assert_close(celsius.convert_delta(10.0, TemperatureScale::Delisle), -15.0);
+        assert_close(TemperatureScale::Fahrenheit.convert_delta(-9.0, TemperatureScale::Rankine),
+                     -9.0);
+
+        //The difference between two converted temperatures is the converted difference.
+        for from in SCALES.iter() {
+            for to in SCALES.iter() {
+                let difference = from.convert(30.0, *to) - from.convert(12.5, *to);
+                assert_close(from.convert_delta(17.5, *to), difference);
+            }
+        }
+    }
+
+    #[test]
+    fn scale_lookup(){
+        assert_eq!(find_scale(" Celcius\n"), Some(TemperatureScale::Celsius));
+        assert_eq!(find_scale("°F"), Some(TemperatureScale::Fahrenheit));
+        assert_eq!(find_scale("K"), Some(TemperatureScale::Kelvin));
+        assert_eq!(find_scale("réaumur"), Some(TemperatureScale::Reaumur));
+        assert_eq!(find_scale("Roemer"), Some(TemperatureScale::Romer));
+        assert_eq!(find_scale("Rø"), Some(TemperatureScale::Romer));
+        assert_eq!(find_scale("de"), Some(TemperatureScale::Delisle));
+        assert_eq!(find_scale("kelvins"), None);
+
+        assert_eq!(rounded(36.99999999999999), 37.0);
+        assert_eq!(rounded(-0.00001).to_string(), "0");
+    }
+}
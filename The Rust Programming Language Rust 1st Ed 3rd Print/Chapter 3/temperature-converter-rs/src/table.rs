@@ -0,0 +1,152 @@
+//Conversion tables, one row per value of a range and one column per scale.
+//Note - The values are from + i * step rather than adding the step up, so -40 to 100 step 0.1
+//          still ends exactly on 100 instead of drifting off by the floating point error.
+
+pub const MAX_ROWS : usize = 10_000;
+pub const DEFAULT_DECIMALS : usize = 2;
+//Past this the digits are floating point noise, and past about 300 fixed cannot scale anymore.
+pub const MAX_DECIMALS : usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableFormat {
+    Text,
+    Markdown,
+    Csv,
+}
+
+pub fn parse_table_format(name : &str) -> Result<TableFormat, String>{
+    match name.trim().to_lowercase().as_str() {
+        "text" => Ok(TableFormat::Text),
+        "markdown" | "md" => Ok(TableFormat::Markdown),
+        "csv" => Ok(TableFormat::Csv),
+        _ => Err(format!("Unknown output '{}', available outputs: text, markdown, csv",
+                         name.trim())),
+    }
+}
+
+pub fn parse_decimals(value : &str) -> Result<usize, String>{
+    match value.trim().parse() {
+        Ok(decimals) if decimals <= MAX_DECIMALS => Ok(decimals),
+        _ => Err(format!("'{}' is not a valid number of decimals, please enter a number between 0 \
+                          and {}!", value.trim(), MAX_DECIMALS)),
+    }
+}
+
+//from, from + step, ... up to and including to, counting down when to is below from.
+pub fn range_values(from : f64, to : f64, step : f64) -> Result<Vec<f64>, String>{
+    if !(from.is_finite() && to.is_finite()) {
+        return Err(format!("The range has to go between two numbers, not {} and {}!", from, to));
+    }
+
+    if !(step > 0.0 && step.is_finite()) {
+        return Err(format!("The step has to be a positive number, not {}!", step));
+    }
+
+    //A tiny bit of slack so 0.1 steps from 0 still reach 1.
+    let steps = ((to - from).abs() / step + 1e-9).floor();
+    if steps >= MAX_ROWS as f64 {
+        return Err(format!("That would be {} rows, please keep it below {}!", steps + 1.0,
+                           MAX_ROWS));
+    }
+
+    let direction = if to < from { -1.0 } else { 1.0 };
+    Ok((0..=steps as usize).map(|index| from + direction * index as f64 * step).collect())
+}
+
+//value with exactly `decimals` decimals, and without a minus sign on zero.
+pub fn fixed(value : f64, decimals : usize) -> String{
+    let scale = 10f64.powi(decimals as i32);
+    format!("{:.*}", decimals, (value * scale).round() / scale + 0.0)
+}
+
+fn text(headers : &[String], rows : &[Vec<String>]) -> String{
+    let widths : Vec<usize> = (0..headers.len())
+        .map(|column| rows.iter().map(|row| &row[column]).chain(Some(&headers[column]))
+            .map(|cell| cell.chars().count()).max().unwrap_or_default())
+        .collect();
+
+    let line = |cells : &[String]| cells.iter().zip(widths.iter())
+        .map(|(cell, width)| format!("{:>width$}", cell, width = width))
+        .collect::<Vec<_>>().join("  ");
+
+    let mut lines = vec![line(headers)];
+    lines.push(widths.iter().map(|width| "-".repeat(*width)).collect::<Vec<_>>().join("  "));
+    lines.extend(rows.iter().map(|row| line(row)));
+    lines.join("\n")
+}
+
+fn markdown(headers : &[String], rows : &[Vec<String>]) -> String{
+    let line = |cells : &[String]| format!("| {} |", cells.join(" | "));
+
+    let mut lines = vec![line(headers)];
+    lines.push(format!("|{}", "---:|".repeat(headers.len())));
+    lines.extend(rows.iter().map(|row| line(row)));
+    lines.join("\n")
+}
+
+fn csv(headers : &[String], rows : &[Vec<String>]) -> String{
+    let mut lines = vec![headers.join(",")];
+    lines.extend(rows.iter().map(|row| row.join(",")));
+    lines.join("\n")
+}
+
+pub fn render(headers : &[String], rows : &[Vec<String>], format : TableFormat) -> String{
+    match format {
+        TableFormat::Text => text(headers, rows),
+        TableFormat::Markdown => markdown(headers, rows),
+        TableFormat::Csv => csv(headers, rows),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranges(){
+        assert_eq!(range_values(-40.0, 0.0, 20.0), Ok(vec![-40.0, -20.0, 0.0]));
+        assert_eq!(range_values(10.0, 0.0, 4.0), Ok(vec![10.0, 6.0, 2.0]));
+        assert_eq!(range_values(5.0, 5.0, 1.0), Ok(vec![5.0]));
+
+        let tenths = range_values(0.0, 1.0, 0.1).unwrap();
+        assert_eq!(tenths.len(), 11);
+        assert_eq!(*tenths.last().unwrap(), 1.0);
+
+        assert!(range_values(0.0, 10.0, 0.0).is_err());
+        assert!(range_values(0.0, 10.0, -1.0).is_err());
+        assert!(range_values(0.0, 1e9, 1.0).is_err());
+        assert!(range_values(f64::NAN, 1.0, 1.0).is_err());
+        assert!(range_values(0.0, f64::NAN, 1.0).is_err());
+        assert!(range_values(f64::NEG_INFINITY, 0.0, 1.0).is_err());
+        assert!(range_values(0.0, 1.0, f64::NAN).is_err());
+    }
+
+    #[test]
+    fn formats(){
+        let headers : Vec<String> = vec![String::from("°C"), String::from("°F")];
+        let rows : Vec<Vec<String>> = [(-40.0, -40.0), (100.0, 212.0)].iter()
+            .map(|(celsius, fahrenheit)| vec![fixed(*celsius, 1), fixed(*fahrenheit, 1)])
+            .collect();
+
+        assert_eq!(render(&headers, &rows, TableFormat::Text),
+                   "   °C     °F\n-----  -----\n-40.0  -40.0\n100.0  212.0");
+        assert_eq!(render(&headers, &rows, TableFormat::Markdown),
+                   "| °C | °F |\n|---:|---:|\n| -40.0 | -40.0 |\n| 100.0 | 212.0 |");
+        assert_eq!(render(&headers, &rows, TableFormat::Csv), "°C,°F\n-40.0,-40.0\n100.0,212.0");
+
+        assert_eq!(fixed(-0.001, 2), "0.00");
+        assert_eq!(fixed(2.675, 0), "3");
+        assert_eq!(fixed(33.8, MAX_DECIMALS), "33.8000000000");
+    }
+
+    #[test]
+    fn decimals(){
+        assert_eq!(parse_decimals("0"), Ok(0));
+        assert_eq!(parse_decimals(" 10 "), Ok(MAX_DECIMALS));
+        assert_eq!(parse_decimals("11"), Err(String::from(
+            "'11' is not a valid number of decimals, please enter a number between 0 and 10!")));
+        assert!(parse_decimals("400").is_err());
+        assert!(parse_decimals("-1").is_err());
+        assert!(parse_decimals("two").is_err());
+    }
+}
@@ -0,0 +1,296 @@
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::{Add, Sub};
+
+use crate::delta::TemperatureDelta;
+use crate::scale::{self, TemperatureScale};
+
+//Temperatures that can only exist at or above absolute zero, one type per scale.
+//Note - A valid temperature always converts to a valid one, so the types convert into each other
+//          with From, only a bare f64 has to go through TryFrom (or new).
+//       Temperature holds any of them when the scale is only known at runtime.
+//       Subtracting two temperatures gives a TemperatureDelta, adding one to a temperature can
+//          end up below absolute zero, so that gives a Result.
+//       Converting through Celsius can land a hair below absolute zero, -218.52 °Ré comes out as
+//          -273.15000000000003 °C, hence the tolerance.
+
+pub const ABSOLUTE_ZERO_TOLERANCE : f64 = 1e-9;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TemperatureError {
+    NotFinite(f64, TemperatureScale),
+    BelowAbsoluteZero(f64, TemperatureScale),
+}
+
+impl fmt::Display for TemperatureError {
+    fn fmt(&self, formatter : &mut fmt::Formatter) -> fmt::Result{
+        match self {
+            TemperatureError::NotFinite(value, scale) => write!(formatter,
+                "{} {} is not a valid temperature", value, scale.symbol()),
+            TemperatureError::BelowAbsoluteZero(value, scale) => write!(formatter,
+                "{} {} is below absolute zero ({} {})", value, scale.symbol(),
+                scale::rounded(TemperatureScale::Kelvin.convert(0.0, *scale)), scale.symbol()),
+        }
+    }
+}
+
+pub fn validate(value : f64, scale : TemperatureScale) -> Result<f64, TemperatureError>{
+    if !value.is_finite() {
+        return Err(TemperatureError::NotFinite(value, scale));
+    }
+
+    if scale.convert(value, TemperatureScale::Kelvin) < -ABSOLUTE_ZERO_TOLERANCE {
+        return Err(TemperatureError::BelowAbsoluteZero(value, scale));
+    }
+
+    Ok(value)
+}
+
+macro_rules! temperatures {
+    ($($name:ident),*) => {
+        $(
+            #[derive(Debug, Clone, Copy, PartialEq)]
+            pub struct $name(f64);
+
+            impl $name {
+                pub const SCALE : TemperatureScale = TemperatureScale::$name;
+
+                pub fn new(value : f64) -> Result<$name, TemperatureError>{
+                    validate(value, $name::SCALE).map($name)
+                }
+
+                pub fn value(self) -> f64{
+                    self.0
+                }
+            }
+
+            impl TryFrom<f64> for $name {
+                type Error = TemperatureError;
+
+                fn try_from(value : f64) -> Result<$name, TemperatureError>{
+                    $name::new(value)
+                }
+            }
+
+            impl fmt::Display for $name {
+                fn fmt(&self, formatter : &mut fmt::Formatter) -> fmt::Result{
+                    write!(formatter, "{} {}", scale::rounded(self.0), $name::SCALE.symbol())
+                }
+            }
+
+            impl Sub for $name {
+                type Output = TemperatureDelta;
+
+                fn sub(self, other : $name) -> TemperatureDelta{
+                    TemperatureDelta::new(self.0 - other.0, $name::SCALE)
+                }
+            }
+
+            impl Add<TemperatureDelta> for $name {
+                type Output = Result<$name, TemperatureError>;
+
+                fn add(self, delta : TemperatureDelta) -> Result<$name, TemperatureError>{
+                    $name::new(self.0 + delta.to($name::SCALE).value())
+                }
+            }
+
+            impl Sub<TemperatureDelta> for $name {
+                type Output = Result<$name, TemperatureError>;
+
+                fn sub(self, delta : TemperatureDelta) -> Result<$name, TemperatureError>{
+                    self + -delta
+                }
+            }
+        )*
+
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub enum Temperature {
+            $($name($name)),*
+        }
+
+        impl Temperature {
+            pub fn new(value : f64, scale : TemperatureScale)
+                       -> Result<Temperature, TemperatureError>{
+                match scale {
+                    $(TemperatureScale::$name => $name::new(value).map(Temperature::$name)),*
+                }
+            }
+
+            pub fn scale(self) -> TemperatureScale{
+                match self {
+                    $(Temperature::$name(_) => $name::SCALE),*
+                }
+            }
+
+            pub fn value(self) -> f64{
+                match self {
+                    $(Temperature::$name(temperature) => temperature.value()),*
+                }
+            }
+
+            pub fn to(self, scale : TemperatureScale) -> Temperature{
+                let celsius = match self {
+                    $(Temperature::$name(temperature) => Celsius::from(temperature)),*
+                };
+
+                match scale {
+                    $(TemperatureScale::$name => Temperature::$name($name::from(celsius))),*
+                }
+            }
+        }
+
+        impl fmt::Display for Temperature {
+            fn fmt(&self, formatter : &mut fmt::Formatter) -> fmt::Result{
+                match self {
+                    $(Temperature::$name(temperature) => temperature.fmt(formatter)),*
+                }
+            }
+        }
+    };
+}
+
+//The runtime versions, in the scale of the left side.
+impl Sub for Temperature {
+    type Output = TemperatureDelta;
+
+    fn sub(self, other : Temperature) -> TemperatureDelta{
+        TemperatureDelta::new(self.value() - other.to(self.scale()).value(), self.scale())
+    }
+}
+
+impl Add<TemperatureDelta> for Temperature {
+    type Output = Result<Temperature, TemperatureError>;
+
+    fn add(self, delta : TemperatureDelta) -> Result<Temperature, TemperatureError>{
+        Temperature::new(self.value() + delta.to(self.scale()).value(), self.scale())
+    }
+}
+
+impl Sub<TemperatureDelta> for Temperature {
+    type Output = Result<Temperature, TemperatureError>;
+
+    fn sub(self, delta : TemperatureDelta) -> Result<Temperature, TemperatureError>{
+        self + -delta
+    }
+}
+
+//From in both directions for every pair of scales, the first one with each of the rest and then
+//the rest among themselves.
+macro_rules! conversions {
+    () => {};
+    ($first:ident $(, $rest:ident)*) => {
+        $(
+            conversion!($first => $rest);
+            conversion!($rest => $first);
+        )*
+        conversions!($($rest),*);
+    };
+}
+
+macro_rules! conversion {
+    ($from:ident => $to:ident) => {
+        impl From<$from> for $to {
+            fn from(temperature : $from) -> $to{
+                $to($from::SCALE.convert(temperature.value(), $to::SCALE))
+            }
+        }
+    };
+}
+
+//A new scale only needs its entry here, next to the variant and formulas in TemperatureScale.
+macro_rules! scales {
+    ($($name:ident),*) => {
+        temperatures!($($name),*);
+        conversions!($($name),*);
+    };
+}
+
+scales!(Celsius, Fahrenheit, Kelvin, Rankine, Reaumur, Romer, Newton, Delisle);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn absolute_zero_is_the_limit(){
+        assert!(Kelvin::new(-5.0).is_err());
+        assert!(Celsius::try_from(-300.0).is_err());
+        assert!(Rankine::new(-0.1).is_err());
+        //Delisle counts downwards, so too cold is too high.
+        assert!(Delisle::new(600.0).is_err());
+        assert!(Delisle::new(-100.0).is_ok());
+
+        assert!(Kelvin::new(0.0).is_ok());
+        assert!(Celsius::new(-273.15).is_ok());
+        assert!(Fahrenheit::new(-459.67).is_ok());
+        assert!(Romer::new(-135.90375).is_ok());
+        assert!(Reaumur::new(-218.52).is_ok());
+        assert!(Newton::new(-90.1395).is_ok());
+        assert!(Delisle::new(559.725).is_ok());
+
+        assert_eq!(Celsius::new(f64::NAN).map_err(|error| error.to_string()),
+                   Err(String::from("NaN °C is not a valid temperature")));
+        assert_eq!(Kelvin::new(-5.0).map_err(|error| error.to_string()),
+                   Err(String::from("-5 K is below absolute zero (0 K)")));
+        assert_eq!(Fahrenheit::new(-500.0).map_err(|error| error.to_string()),
+                   Err(String::from("-500 °F is below absolute zero (-459.67 °F)")));
+    }
+
+    #[test]
+    fn conversions_between_types(){
+        let boiling = Celsius::new(100.0).unwrap();
+
+        assert_eq!(Fahrenheit::from(boiling).to_string(), "212 °F");
+        assert_eq!(Kelvin::from(boiling).to_string(), "373.15 K");
+        assert_eq!(Delisle::from(boiling).to_string(), "0 °De");
+
+        let body : Celsius = Fahrenheit::new(98.6).unwrap().into();
+        assert_eq!(body.to_string(), "37 °C");
+        assert_eq!(Kelvin::from(Fahrenheit::new(-459.67).unwrap()).to_string(), "0 K");
+        //The last pair of the list, generated like every other one.
+        assert_eq!(Newton::from(Delisle::new(0.0).unwrap()).to_string(), "33 °N");
+        assert_eq!(Delisle::from(Newton::new(33.0).unwrap()).to_string(), "0 °De");
+    }
+
+    #[test]
+    fn runtime_scales(){
+        let temperature = Temperature::new(-40.0, TemperatureScale::Celsius).unwrap();
+        let fahrenheit = temperature.to(TemperatureScale::Fahrenheit);
+
+        assert_eq!(fahrenheit.scale(), TemperatureScale::Fahrenheit);
+        assert_eq!(fahrenheit.to_string(), "-40 °F");
+        assert_eq!(temperature.to(TemperatureScale::Rankine).to_string(), "419.67 °R");
+        assert!(Temperature::new(-1.0, TemperatureScale::Kelvin).is_err());
+    }
+
+    #[test]
+    fn arithmetic_with_deltas(){
+        let warm = Celsius::new(30.0).unwrap();
+        let cold = Celsius::new(20.0).unwrap();
+
+        let change = warm - cold;
+        assert_eq!(change.to_string(), "10 °C");
+        assert_eq!(change.to(TemperatureScale::Fahrenheit).to_string(), "18 °F");
+
+        let fahrenheit = TemperatureDelta::new(18.0, TemperatureScale::Fahrenheit);
+        assert_eq!((cold + fahrenheit).map(|temperature| temperature.to_string()),
+                   Ok(String::from("30 °C")));
+        assert_eq!((warm - fahrenheit).map(|temperature| temperature.to_string()),
+                   Ok(String::from("20 °C")));
+        assert!((Kelvin::new(5.0).unwrap() - TemperatureDelta::new(10.0, TemperatureScale::Kelvin))
+            .is_err());
+
+        //Mixed scales at runtime, the result is in the scale of the left side.
+        let body = Temperature::new(98.6, TemperatureScale::Fahrenheit).unwrap();
+        let room = Temperature::new(20.0, TemperatureScale::Celsius).unwrap();
+        assert_eq!((body - room).to_string(), "30.6 °F");
+        assert_eq!((room - body).to_string(), "-17 °C");
+        assert_eq!((room + (body - room)).map(|temperature| temperature.to_string()),
+                   Ok(String::from("37 °C")));
+        assert!((room - TemperatureDelta::new(300.0, TemperatureScale::Kelvin)).is_err());
+
+        let delta = TemperatureDelta::new(10.0, TemperatureScale::Celsius)
+            - TemperatureDelta::new(9.0, TemperatureScale::Fahrenheit);
+        assert_eq!(delta.to_string(), "5 °C");
+    }
+}
@@ -0,0 +1,166 @@
+use std::fs;
+
+use crate::locale::Locale;
+use crate::lyrics;
+use crate::melody;
+
+//Timed lyrics for karaoke screens, as LRC or SRT files.
+//Note - Each line lasts as long as its melody phrase at the chosen tempo, with the same bar of
+//          rest between verses as the MIDI export, so the lyrics stay in sync with the melody.
+
+pub struct TimedLine {
+    pub start_ms : u64,
+    pub end_ms : u64,
+    pub text : String,
+}
+
+fn eighth_duration_ms(tempo_bpm : u32) -> f64{
+    60_000.0 / tempo_bpm.max(1) as f64 / 2.0
+}
+
+pub fn timed_lines(locale : &Locale, verses : &[usize], tempo_bpm : u32) -> Vec<TimedLine>{
+    let eighth_ms = eighth_duration_ms(tempo_bpm);
+    let mut timed_lines = Vec::new();
+    let mut elapsed_eighths : u32 = 0;
+
+    for (index, verse_number) in verses.iter().enumerate() {
+        if index > 0 {
+            elapsed_eighths += melody::VERSE_GAP_EIGHTHS;
+        }
+
+        for (line, part) in lyrics::sung_lines(locale, *verse_number) {
+            let start_eighths = elapsed_eighths;
+            elapsed_eighths += melody::phrase_eighths(melody::line_phrase(part));
+
+            //Missing lines (nothing translated) still take up their time, like an instrumental bar.
+            if let Some(text) = line {
+                timed_lines.push(TimedLine {
+                    start_ms : (start_eighths as f64 * eighth_ms).round() as u64,
+                    end_ms : (elapsed_eighths as f64 * eighth_ms).round() as u64,
+                    text,
+                });
+            }
+        }
+    }
+
+    timed_lines
+}
+
+//[mm:ss.xx], in hundredths of a second.
+fn lrc_timestamp(ms : u64) -> String{
+    format!("[{:02}:{:02}.{:02}]", ms / 60_000, ms / 1000 % 60, ms % 1000 / 10)
+}
+
+//hh:mm:ss,mmm
+fn srt_timestamp(ms : u64) -> String{
+    format!("{:02}:{:02}:{:02},{:03}", ms / 3_600_000, ms / 60_000 % 60, ms / 1000 % 60, ms % 1000)
+}
+
+pub fn lrc_text(title : &str, timed_lines : &[TimedLine]) -> String{
+    let mut text = format!("[ti:{}]\n", title);
+
+    for line in timed_lines {
+        text.push_str(&format!("{}{}\n", lrc_timestamp(line.start_ms), line.text));
+    }
+
+    text
+}
+
+pub fn srt_text(timed_lines : &[TimedLine]) -> String{
+    let mut text = String::new();
+
+    for (index, line) in timed_lines.iter().enumerate() {
+        text.push_str(&format!("{}\n{} --> {}\n{}\n\n", index + 1, srt_timestamp(line.start_ms),
+                               srt_timestamp(line.end_ms), line.text));
+    }
+
+    text
+}
+
+pub fn write_lrc_file(path : &str, locale : &Locale, verses : &[usize], tempo_bpm : u32)
+                      -> Result<(), String>{
+    fs::write(path, lrc_text(locale.title, &timed_lines(locale, verses, tempo_bpm)))
+        .map_err(|error| format!("Failed to write LRC file '{}': {}", path, error))
+}
+
+pub fn write_srt_file(path : &str, locale : &Locale, verses : &[usize], tempo_bpm : u32)
+                      -> Result<(), String>{
+    fs::write(path, srt_text(&timed_lines(locale, verses, tempo_bpm)))
+        .map_err(|error| format!("Failed to write SRT file '{}': {}", path, error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::locale;
+
+    #[test]
+    fn timestamps(){
+        assert_eq!(lrc_timestamp(0), "[00:00.00]");
+        assert_eq!(lrc_timestamp(4_250), "[00:04.25]");
+        assert_eq!(lrc_timestamp(83_456), "[01:23.45]");
+        assert_eq!(lrc_timestamp(3_723_456), "[62:03.45]");
+
+        assert_eq!(srt_timestamp(0), "00:00:00,000");
+        assert_eq!(srt_timestamp(83_456), "00:01:23,456");
+        assert_eq!(srt_timestamp(3_723_456), "01:02:03,456");
+    }
+
+    #[test]
+    fn lines_follow_the_melody(){
+        //At 120 bpm an eighth note lasts 250 ms.
+        let lines = timed_lines(&locale::ENGLISH, &[1, 2], 120);
+        let eighths = |phrase| melody::phrase_eighths(phrase) as u64 * 250;
+
+        assert_eq!(lines.len(), 5);
+        assert_eq!((lines[0].start_ms, lines[0].end_ms), (0, eighths(melody::intro_phrase())));
+        assert_eq!(lines[1].text, "A partridge in a pear tree");
+        assert_eq!(lines[1].end_ms - lines[1].start_ms, eighths(melody::gift_phrase(1)));
+
+        //Same rest between the verses as in the MIDI file.
+        assert_eq!(lines[2].start_ms - lines[1].end_ms,
+                   melody::VERSE_GAP_EIGHTHS as u64 * 250);
+        assert_eq!(lines[2].text, "On the second day of Christmas, my true love sent to me");
+
+        for pair in lines.windows(2).skip(2) {
+            assert_eq!(pair[0].end_ms, pair[1].start_ms);
+        }
+
+        //Every line of the verse lasts exactly as long as its phrase in the MIDI file.
+        let lines = timed_lines(&locale::ENGLISH, &[12], 120);
+        let phrases = melody::verse_phrases(12);
+        assert_eq!(lines.len(), phrases.len());
+        for (line, phrase) in lines.iter().zip(phrases) {
+            assert_eq!(line.end_ms - line.start_ms, eighths(phrase));
+        }
+    }
+
+    #[test]
+    fn tempo_scales_the_timing(){
+        let fast = timed_lines(&locale::ENGLISH, &[3, 4], 120);
+        let slow = timed_lines(&locale::ENGLISH, &[3, 4], 60);
+
+        assert_eq!(fast.len(), slow.len());
+        for (fast, slow) in fast.iter().zip(slow.iter()) {
+            assert_eq!((slow.start_ms, slow.end_ms), (fast.start_ms * 2, fast.end_ms * 2));
+        }
+    }
+
+    #[test]
+    fn file_formats(){
+        let lines = timed_lines(&locale::ENGLISH, &[2], 120);
+
+        let lrc = lrc_text("Title", &lines);
+        assert!(lrc.starts_with("[ti:Title]\n[00:00.00]On the second day of Christmas"));
+        assert_eq!(lrc.lines().count(), 4);
+
+        let srt = srt_text(&lines);
+        let cues : Vec<&str> = srt.split("\n\n").filter(|cue| !cue.is_empty()).collect();
+        assert_eq!(cues.len(), 3);
+        assert_eq!(cues[0], "1\n00:00:00,000 --> 00:00:04,250\n\
+                             On the second day of Christmas, my true love sent to me");
+        assert!(cues[1].starts_with("2\n00:00:04,250 --> "));
+        assert!(cues[2].starts_with("3\n"));
+        assert!(cues[2].ends_with("\nA partridge in a pear tree"));
+    }
+}
@@ -0,0 +1,356 @@
+//Locale packs for the song lyrics.
+//Note - Every locale only types the words, the lines themselves are put together by the lyrics
+//          module from the templates below.
+//       Ordinals agree with the grammatical gender of the word for "day", and the count at the
+//          start of each gift line agrees with the gift (e.g. "un" or "une", "a" or "an").
+//       Anything missing from a locale (a shorter table) falls back to the English entry, English
+//          itself generates its numbers and ordinals so a song can go past twelve days.
+
+use crate::numbers;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Gender {
+    Masculine,
+    Feminine,
+    Neuter,
+}
+
+pub struct Gift<'a> {
+    pub gender : Gender,
+    pub name : &'a str,
+}
+
+pub struct Locale<'a> {
+    pub code : &'static str,
+    pub title : &'static str,
+    pub day_gender : Gender,
+    //Index 0 is unused, so the index matches the verse number.
+    pub ordinals : &'static [&'static str],
+    pub cardinals : &'static [&'static str],
+    pub gifts : &'a [Gift<'a>],
+    //Agreement hooks, turning the base word from the tables into the form used in the line.
+    pub agree_ordinal : fn(&str, Gender) -> String,
+    pub agree_cardinal : fn(usize, &str, &Gift) -> String,
+    //Line templates, {ordinal}, {count} and {gift} are replaced by the lyrics module.
+    pub verse_intro : &'static str,
+    pub gift_line : &'static str,
+    //Used for the second gift, which is always followed by the last line of the verse.
+    pub penultimate_line : &'static str,
+    //Used for the first gift when it closes a verse with more than one gift.
+    pub closing_line : &'static str,
+}
+
+const fn gift(gender : Gender, name : &str) -> Gift<'_>{
+    Gift { gender, name }
+}
+
+fn no_agreement(word : &str, _gender : Gender) -> String{
+    word.to_string()
+}
+
+//Note - "an" goes by the first letter only, so "an apple" but also "a hour".
+fn english_cardinal(count : usize, word : &str, gift : &Gift) -> String{
+    let starts_with_vowel = gift.name.chars().next()
+        .is_some_and(|first| "aeiou".contains(first.to_ascii_lowercase()));
+
+    match count {
+        1 if starts_with_vowel => String::from("an"),
+        1 => String::from("a"),
+        _ => word.to_string(),
+    }
+}
+
+//Based on Twelve Days of Christmas provided by Genius
+//Source: https://genius.com/Christmas-songs-the-twelve-days-of-christmas-lyrics
+pub const ENGLISH : Locale<'static> = Locale {
+    code : "en",
+    title : "The Twelve Days of Christmas",
+    day_gender : Gender::Neuter,
+    //Generated by the numbers module.
+    ordinals : &[],
+    cardinals : &[],
+    gifts : &[gift(Gender::Neuter, ""), gift(Gender::Neuter, "partridge in a pear tree"),
+        gift(Gender::Neuter, "turtle doves"), gift(Gender::Neuter, "french hens"),
+        gift(Gender::Neuter, "calling birds"), gift(Gender::Neuter, "golden rings"),
+        gift(Gender::Neuter, "geese a-laying"), gift(Gender::Neuter, "swans a-swimming"),
+        gift(Gender::Neuter, "maids a-milking"), gift(Gender::Neuter, "ladies dancing"),
+        gift(Gender::Neuter, "lords a-leaping"), gift(Gender::Neuter, "pipers piping"),
+        gift(Gender::Neuter, "drummers drumming")],
+    agree_ordinal : no_agreement,
+    agree_cardinal : english_cardinal,
+    verse_intro : "On the {ordinal} day of Christmas, my true love sent to me",
+    gift_line : "{count} {gift}",
+    penultimate_line : "{count} {gift}, and",
+    closing_line : "{count} {gift}",
+};
+
+//Note - Only "premier" changes with the gender, "jour" is masculine anyway.
+fn french_ordinal(word : &str, gender : Gender) -> String{
+    match (word, gender) {
+        ("premier", Gender::Feminine) => String::from("première"),
+        _ => word.to_string(),
+    }
+}
+
+fn french_cardinal(count : usize, word : &str, gift : &Gift) -> String{
+    match (count, gift.gender) {
+        (1, Gender::Feminine) => String::from("une"),
+        _ => word.to_string(),
+    }
+}
+
+pub const FRENCH : Locale<'static> = Locale {
+    code : "fr",
+    title : "Les douze jours de Noël",
+    day_gender : Gender::Masculine,
+    ordinals : &["", "premier", "deuxième", "troisième", "quatrième", "cinquième", "sixième",
+        "septième", "huitième", "neuvième", "dixième", "onzième", "douzième"],
+    cardinals : &["", "un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf", "dix",
+        "onze", "douze"],
+    gifts : &[gift(Gender::Feminine, ""), gift(Gender::Feminine, "perdrix dans un poirier"),
+        gift(Gender::Feminine, "tourterelles"), gift(Gender::Feminine, "poules françaises"),
+        gift(Gender::Masculine, "oiseaux qui chantent"), gift(Gender::Masculine, "anneaux d'or"),
+        gift(Gender::Feminine, "oies qui pondent"), gift(Gender::Masculine, "cygnes qui nagent"),
+        gift(Gender::Feminine, "servantes qui traient"),
+        gift(Gender::Feminine, "dames qui dansent"),
+        gift(Gender::Masculine, "seigneurs qui sautent"),
+        gift(Gender::Masculine, "joueurs de cornemuse"),
+        gift(Gender::Masculine, "tambours qui battent")],
+    agree_ordinal : french_ordinal,
+    agree_cardinal : french_cardinal,
+    verse_intro : "Le {ordinal} jour de Noël, mon véritable amour m'a donné",
+    gift_line : "{count} {gift}",
+    penultimate_line : "{count} {gift}",
+    closing_line : "et {count} {gift}",
+};
+
+//Note - "primero" and "tercero" drop the final o in front of a masculine noun ("el primer día"),
+//       and every ordinal ends in a instead of o for a feminine one.
+fn spanish_ordinal(word : &str, gender : Gender) -> String{
+    match gender {
+        Gender::Feminine => match word.strip_suffix('o') {
+            Some(stem) => format!("{}a", stem),
+            None => word.to_string(),
+        },
+        _ => match word {
+            "primero" | "tercero" => word.trim_end_matches('o').to_string(),
+            _ => word.to_string(),
+        },
+    }
+}
+
+fn spanish_cardinal(count : usize, word : &str, gift : &Gift) -> String{
+    match (count, gift.gender) {
+        (1, Gender::Feminine) => String::from("una"),
+        (1, _) => String::from("un"),
+        _ => word.to_string(),
+    }
+}
+
+pub const SPANISH : Locale<'static> = Locale {
+    code : "es",
+    title : "Los doce días de Navidad",
+    day_gender : Gender::Masculine,
+    ordinals : &["", "primero", "segundo", "tercero", "cuarto", "quinto", "sexto", "séptimo",
+        "octavo", "noveno", "décimo", "undécimo", "duodécimo"],
+    cardinals : &["", "uno", "dos", "tres", "cuatro", "cinco", "seis", "siete", "ocho", "nueve",
+        "diez", "once", "doce"],
+    gifts : &[gift(Gender::Feminine, ""), gift(Gender::Feminine, "perdiz en un peral"),
+        gift(Gender::Feminine, "tórtolas"), gift(Gender::Feminine, "gallinas francesas"),
+        gift(Gender::Masculine, "pájaros cantores"), gift(Gender::Masculine, "anillos de oro"),
+        gift(Gender::Feminine, "ocas poniendo huevos"), gift(Gender::Masculine, "cisnes nadando"),
+        gift(Gender::Feminine, "doncellas ordeñando"), gift(Gender::Feminine, "damas bailando"),
+        gift(Gender::Masculine, "señores saltando"), gift(Gender::Masculine, "gaiteros tocando"),
+        gift(Gender::Masculine, "tamborileros tocando")],
+    agree_ordinal : spanish_ordinal,
+    agree_cardinal : spanish_cardinal,
+    verse_intro : "El {ordinal} día de Navidad, mi verdadero amor me regaló",
+    gift_line : "{count} {gift}",
+    penultimate_line : "{count} {gift}",
+    closing_line : "y {count} {gift}",
+};
+
+//Note - The table only has the stems, after "am" (an dem) the ordinal always takes the weak
+//          dative ending -en, whatever the gender.
+fn german_ordinal(word : &str, _gender : Gender) -> String{
+    format!("{}en", word)
+}
+
+fn german_cardinal(count : usize, word : &str, gift : &Gift) -> String{
+    match (count, gift.gender) {
+        (1, Gender::Feminine) => String::from("eine"),
+        (1, _) => String::from("ein"),
+        _ => word.to_string(),
+    }
+}
+
+pub const GERMAN : Locale<'static> = Locale {
+    code : "de",
+    title : "Die zwölf Weihnachtstage",
+    day_gender : Gender::Masculine,
+    ordinals : &["", "erst", "zweit", "dritt", "viert", "fünft", "sechst", "siebt", "acht", "neunt",
+        "zehnt", "elft", "zwölft"],
+    cardinals : &["", "eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun",
+        "zehn", "elf", "zwölf"],
+    gifts : &[gift(Gender::Neuter, ""), gift(Gender::Neuter, "Rebhuhn im Birnbaum"),
+        gift(Gender::Feminine, "Turteltauben"), gift(Gender::Neuter, "französische Hühner"),
+        gift(Gender::Masculine, "singende Vögel"), gift(Gender::Masculine, "goldene Ringe"),
+        gift(Gender::Feminine, "legende Gänse"), gift(Gender::Masculine, "schwimmende Schwäne"),
+        gift(Gender::Feminine, "melkende Mägde"), gift(Gender::Feminine, "tanzende Damen"),
+        gift(Gender::Masculine, "springende Lords"), gift(Gender::Masculine, "pfeifende Pfeifer"),
+        gift(Gender::Masculine, "trommelnde Trommler")],
+    agree_ordinal : german_ordinal,
+    agree_cardinal : german_cardinal,
+    verse_intro : "Am {ordinal} Weihnachtstag schenkte mir mein Liebster",
+    gift_line : "{count} {gift}",
+    penultimate_line : "{count} {gift}",
+    closing_line : "und {count} {gift}",
+};
+
+pub const LOCALES : [&Locale<'static>; 4] = [&ENGLISH, &FRENCH, &SPANISH, &GERMAN];
+
+pub fn find_locale(code : &str) -> Option<&'static Locale<'static>>{
+    LOCALES.iter().copied().find(|locale| locale.code.eq_ignore_ascii_case(code.trim()))
+}
+
+//Reads a custom gift list, one gift per line and without its count, e.g. "turtle doves".
+//Note - A count typed in anyway ("Two turtle doves") is dropped, since the count is generated.
+//       Custom gifts have no grammatical gender, so the count words use the neutral form.
+pub fn parse_gift_list(contents : &str) -> Result<Vec<Gift<'_>>, String>{
+    let mut gifts = vec![gift(Gender::Neuter, "")];
+
+    for line in contents.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        let day = gifts.len();
+        let is_count = |word : &str| {
+            let word = word.to_lowercase();
+            word == numbers::cardinal(day) || (day == 1 && (word == "a" || word == "an"))
+        };
+
+        let name = match line.split_once(' ') {
+            Some((first_word, rest)) if is_count(first_word) => rest.trim(),
+            _ => line,
+        };
+        gifts.push(gift(Gender::Neuter, name));
+    }
+
+    if gifts.len() < 2 {
+        return Err(String::from("The gift list is empty, please put one gift per line!"));
+    }
+
+    Ok(gifts)
+}
+
+impl<'a> Locale<'a> {
+    //Number of days in the song, one per gift.
+    pub fn days(&self) -> usize{
+        self.gifts.len().saturating_sub(1)
+    }
+
+    //Ordinal for the verse intro, e.g. "first", "premier" or "ersten".
+    pub fn ordinal(&self, verse_number : usize) -> Option<String>{
+        if verse_number == 0 {
+            return None;
+        }
+
+        match self.ordinals.get(verse_number) {
+            Some(word) => Some((self.agree_ordinal)(word, self.day_gender)),
+            None => Some((ENGLISH.agree_ordinal)(&numbers::ordinal(verse_number),
+                                                 ENGLISH.day_gender)),
+        }
+    }
+
+    //Count at the start of a gift line, e.g. "a", "two", "une" or "zwei".
+    pub fn count_word(&self, count : usize, gift : &Gift) -> Option<String>{
+        if count == 0 {
+            return None;
+        }
+
+        match self.cardinals.get(count) {
+            Some(word) => Some((self.agree_cardinal)(count, word, gift)),
+            None => Some((ENGLISH.agree_cardinal)(count, &numbers::cardinal(count), gift)),
+        }
+    }
+
+    pub fn gift(&self, day : usize) -> Option<&Gift<'a>>{
+        match self.gifts.get(day) {
+            Some(gift) => Some(gift),
+            None if self.code != ENGLISH.code => ENGLISH.gift(day),
+            None => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ordinals_agree_with_the_day(){
+        assert_eq!(french_ordinal("premier", Gender::Masculine), "premier");
+        assert_eq!(french_ordinal("premier", Gender::Feminine), "première");
+        assert_eq!(french_ordinal("deuxième", Gender::Feminine), "deuxième");
+
+        assert_eq!(spanish_ordinal("primero", Gender::Masculine), "primer");
+        assert_eq!(spanish_ordinal("tercero", Gender::Masculine), "tercer");
+        assert_eq!(spanish_ordinal("segundo", Gender::Masculine), "segundo");
+        assert_eq!(spanish_ordinal("primero", Gender::Feminine), "primera");
+        assert_eq!(spanish_ordinal("séptimo", Gender::Feminine), "séptima");
+
+        assert_eq!(FRENCH.ordinal(1).as_deref(), Some("premier"));
+        assert_eq!(SPANISH.ordinal(3).as_deref(), Some("tercer"));
+        assert_eq!(GERMAN.ordinal(1).as_deref(), Some("ersten"));
+        assert_eq!(GERMAN.ordinal(12).as_deref(), Some("zwölften"));
+        assert_eq!(ENGLISH.ordinal(12).as_deref(), Some("twelfth"));
+        assert_eq!(ENGLISH.ordinal(0), None);
+    }
+
+    #[test]
+    fn counts_agree_with_the_gift(){
+        assert_eq!(FRENCH.count_word(1, &gift(Gender::Masculine, "x")).as_deref(), Some("un"));
+        assert_eq!(FRENCH.count_word(1, &gift(Gender::Feminine, "x")).as_deref(), Some("une"));
+        assert_eq!(SPANISH.count_word(1, &gift(Gender::Masculine, "x")).as_deref(), Some("un"));
+        assert_eq!(SPANISH.count_word(1, &gift(Gender::Feminine, "x")).as_deref(), Some("una"));
+        assert_eq!(GERMAN.count_word(1, &gift(Gender::Neuter, "x")).as_deref(), Some("ein"));
+        assert_eq!(GERMAN.count_word(1, &gift(Gender::Feminine, "x")).as_deref(), Some("eine"));
+        assert_eq!(GERMAN.count_word(2, &gift(Gender::Feminine, "x")).as_deref(), Some("zwei"));
+        assert_eq!(ENGLISH.count_word(1, &gift(Gender::Neuter, "pear")).as_deref(), Some("a"));
+        assert_eq!(ENGLISH.count_word(1, &gift(Gender::Neuter, "Apple")).as_deref(), Some("an"));
+        assert_eq!(ENGLISH.count_word(2, &gift(Gender::Neuter, "apples")).as_deref(), Some("two"));
+        assert_eq!(ENGLISH.count_word(0, &gift(Gender::Neuter, "x")), None);
+    }
+
+    #[test]
+    fn gift_lists_drop_the_typed_counts(){
+        let gifts = parse_gift_list("An apple\n\nTwo flaky tests\n  three green beans  \n\
+                                     four\nfive-a-side teams\nSix\n").unwrap();
+        let names : Vec<&str> = gifts.iter().map(|gift| gift.name).collect();
+
+        assert_eq!(names, ["", "apple", "flaky tests", "green beans", "four", "five-a-side teams",
+            "Six"]);
+        assert!(parse_gift_list("").is_err());
+        assert!(parse_gift_list("\n   \n").is_err());
+
+        let custom = Locale { gifts : &gifts, ..ENGLISH };
+        assert_eq!(custom.count_word(1, &gifts[1]).as_deref(), Some("an"));
+        assert_eq!(custom.count_word(2, &gifts[2]).as_deref(), Some("two"));
+    }
+
+    #[test]
+    fn missing_entries_fall_back_to_english(){
+        assert!(find_locale("FR").is_some());
+        assert!(find_locale("xx").is_none());
+
+        let gifts : Vec<Gift> = (0..=14).map(|_| gift(Gender::Masculine, "cadeaux")).collect();
+        let long_song = Locale { gifts : &gifts, ..FRENCH };
+
+        assert_eq!(long_song.days(), 14);
+        assert_eq!(long_song.ordinal(12).as_deref(), Some("douzième"));
+        assert_eq!(long_song.ordinal(13).as_deref(), Some("thirteenth"));
+        assert_eq!(long_song.count_word(14, &gifts[14]).as_deref(), Some("fourteen"));
+
+        //Past its own gifts a locale borrows the English ones.
+        assert_eq!(FRENCH.gift(13).map(|gift| gift.name), None);
+        assert_eq!(Locale { gifts : &[], ..FRENCH }.gift(2).map(|gift| gift.name),
+                   Some("turtle doves"));
+    }
+}
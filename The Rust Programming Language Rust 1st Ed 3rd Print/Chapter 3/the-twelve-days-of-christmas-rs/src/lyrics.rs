@@ -0,0 +1,78 @@
+use crate::locale::Locale;
+
+//Puts the song lines together from the words and templates of a locale.
+
+//Part of the verse a line is, the melody has a phrase for each.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Line {
+    Intro,
+    Gift(usize),
+}
+
+fn capitalize(line : &str) -> String{
+    let mut characters = line.chars();
+
+    match characters.next() {
+        Some(first) => first.to_uppercase().chain(characters).collect(),
+        None => String::new(),
+    }
+}
+
+//First line of a verse, e.g. "On the first day of Christmas, my true love sent to me".
+pub fn intro_line(locale : &Locale, verse_number : usize) -> Option<String>{
+    let ordinal = locale.ordinal(verse_number)?;
+
+    Some(capitalize(&locale.verse_intro.replace("{ordinal}", &ordinal)))
+}
+
+fn fill_gift_template(locale : &Locale, template : &str, day : usize) -> Option<String>{
+    let gift = locale.gift(day)?;
+    let count = locale.count_word(day, gift)?;
+
+    Some(capitalize(&template.replace("{count}", &count).replace("{gift}", gift.name)))
+}
+
+//Gift line as it is sung within a verse, e.g. "Two turtle doves, and".
+pub fn gift_line(locale : &Locale, day : usize, verse_number : usize) -> Option<String>{
+    let template = if day == 1 && verse_number > 1 {
+        locale.closing_line
+    } else if day == 2 {
+        locale.penultimate_line
+    } else {
+        locale.gift_line
+    };
+
+    fill_gift_template(locale, template, day)
+}
+
+//Gift line on its own, without the joining words, e.g. "Two turtle doves".
+pub fn gift_label(locale : &Locale, day : usize) -> Option<String>{
+    fill_gift_template(locale, locale.gift_line, day)
+}
+
+//Lines of a verse in the order they are sung, the intro first and then each gift counting down
+//to the first one.
+//Note - The console, the karaoke export and the melody all follow this order, so they stay in step.
+pub fn verse_structure(verse_number : usize) -> Vec<Line>{
+    let mut lines = vec![Line::Intro];
+    lines.extend((1..=verse_number).rev().map(Line::Gift));
+    lines
+}
+
+//Every line of a verse with its part, the text is None when the locale has no words for it.
+pub fn sung_lines(locale : &Locale, verse_number : usize) -> Vec<(Option<String>, Line)>{
+    verse_structure(verse_number).into_iter()
+        .map(|line| match line {
+            Line::Intro => (intro_line(locale, verse_number), line),
+            Line::Gift(day) => (gift_line(locale, day, verse_number), line),
+        })
+        .collect()
+}
+
+//Gift lines of a verse, counting down from the newest gift to the first one.
+pub fn verse_lines(locale : &Locale, verse_number : usize) -> Vec<String>{
+    sung_lines(locale, verse_number).into_iter()
+        .filter(|(_, line)| *line != Line::Intro)
+        .filter_map(|(text, _)| text)
+        .collect()
+}
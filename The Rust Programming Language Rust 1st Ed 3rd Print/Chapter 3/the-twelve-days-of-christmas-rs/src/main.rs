@@ -1,45 +1,228 @@
-use std::io;
+use std::env;
+use std::fs;
+use std::process;
 
-fn ordinal_numbers_output(verse_number : usize){
-    let ordinal_numbers : [&str; 13] = ["", "first", "second", "third", "fourth", "fifth", "sixth",
-        "seventh", "eighth", "ninth", "tenth", "eleventh", "twelfth"];
+use locale::Locale;
 
-    println!("On the {} day of Christmas, my true love sent to me",
-             ordinal_numbers[verse_number]);
+mod locale;
+mod karaoke;
+mod lyrics;
+mod melody;
+mod midi;
+mod numbers;
+mod prices;
+mod prompt;
+mod stats;
+
+const DEFAULT_TEMPO_BPM : u32 = 120;
+const MAX_TEMPO_BPM : u32 = 600;
+
+fn ordinal_numbers_output(locale : &Locale, verse_number : usize){
+    if let Some(line) = lyrics::intro_line(locale, verse_number) {
+        println!("{}", line);
+    }
 }
 
-fn verse_output(verse_number : usize){
-    //Based on Twelve Days of Christmas provided by Genius
-    //Source: https://genius.com/Christmas-songs-the-twelve-days-of-christmas-lyrics
-    let song_lyrics : [&str; 13] = ["", "A partridge in a pear tree", "Two turtle doves, and",
-        "Three french hens", "Four calling birds", "Five golden rings", "Six geese a-laying",
-        "Seven swans a-swimming", "Eight maids a-milking", "Nine ladies dancing",
-        "Ten lords a-leaping", "Eleven pipers piping", "Twelve drummers drumming"];
+fn verse_output(locale : &Locale, verse_number : usize){
+    for line in lyrics::verse_lines(locale, verse_number) {
+        println!("{}", line);
+    }
+}
 
-    let mut counter: usize = verse_number;
+//Removes `--name value` from the arguments wherever it is, returning the value if it was given.
+fn take_option(arguments : &mut Vec<String>, name : &str) -> Result<Option<String>, String>{
+    match arguments.iter().position(|argument| argument == name) {
+        None => Ok(None),
+        Some(index) if index + 1 < arguments.len() => {
+            let value = arguments.remove(index + 1);
+            arguments.remove(index);
+            Ok(Some(value))
+        }
+        Some(_) => Err(format!("Missing value for {}", name)),
+    }
+}
 
-    while counter > 0 {
-        println!("{}", song_lyrics[counter]);
+//Picks the locale for --lang, falling back to English when the language is not translated yet.
+fn select_locale(code : Option<String>) -> &'static Locale<'static>{
+    let code = match code {
+        Some(code) => code,
+        None => return &locale::ENGLISH,
+    };
 
-        counter -= 1;
+    match locale::find_locale(&code) {
+        Some(locale) => locale,
+        None => {
+            let available : Vec<String> = locale::LOCALES.iter()
+                .map(|locale| format!("{} ({})", locale.code, locale.title))
+                .collect();
+            println!("No translation for '{}', falling back to English. Available: {}",
+                     code, available.join(", "));
+            &locale::ENGLISH
+        }
     }
 }
 
-fn main() {
-    println!("Welcome to The Twelve Days of Christmas Program!");
-    println!("Please enter the verse you want to choose for the lyrics [Verse 1 - 12].");
+//Usage: the-twelve-days-of-christmas-rs stats [days]
+fn stats_mode(locale : &Locale, days_argument : Option<String>) -> Result<(), String>{
+    let days : usize = match days_argument {
+        None => locale.days(),
+        Some(days) => days.trim().parse()
+            .map_err(|_| format!("Not a valid number of days: {}", days))?,
+    };
+
+    if days == 0 || days > stats::MAX_STATS_DAYS {
+        return Err(format!("Invalid number of days, please enter number between 1 and {}!",
+                           stats::MAX_STATS_DAYS));
+    }
+
+    //Days past the translated (or typed) ones have no lyrics to borrow from.
+    stats::stats_output(days, |gift| lyrics::gift_label(locale, gift)
+        .unwrap_or_else(|| format!("Gift of day {}", gift)));
+    Ok(())
+}
+
+//Usage: the-twelve-days-of-christmas-rs prices <file> [--year YEAR] [--verse N]
+//                                                [--compare FROM_YEAR TO_YEAR]
+//Note - --verse is taken out of the arguments by run, like for the lyrics, and handed in here.
+fn prices_mode(arguments : Vec<String>, verse_option : Option<String>) -> Result<(), String>{
+    let mut arguments = arguments.into_iter();
+    let path = arguments.next().ok_or("Missing price file, usage: prices <file>")?;
+    let table = prices::PriceTable::load(&path)?;
+
+    let mut year : Option<String> = None;
+    let mut comparison : Option<(String, String)> = None;
+
+    let verse_number = match verse_option {
+        None => None,
+        Some(value) => match value.trim().parse() {
+            Ok(verse) if verse > 0 && verse <= table.days() => Some(verse),
+            _ => return Err(format!("Invalid verse number {}, please enter number between 1 and \
+                                     {}!", value, table.days())),
+        },
+    };
+
+    while let Some(option) = arguments.next() {
+        match option.as_str() {
+            "--year" => year = Some(arguments.next().ok_or("Missing value for --year")?),
+            "--compare" => {
+                let from_year = arguments.next().ok_or("Missing years for --compare")?;
+                let to_year = arguments.next().ok_or("Missing second year for --compare")?;
+                comparison = Some((from_year, to_year));
+            }
+            _ => return Err(format!("Unknown option '{}'", option)),
+        }
+    }
+
+    match comparison {
+        Some((from_year, to_year)) =>
+            prices::comparison_output(&table, &from_year, &to_year, verse_number),
+        None => {
+            //Defaults to the most recent year, which is the last column of the file.
+            let year = year.unwrap_or_else(|| table.years[table.years.len() - 1].clone());
+            prices::prices_output(&table, &year, verse_number)
+        }
+    }
+}
+
+//Usage: --verse <verse | all>, where the verse is anything the interactive prompt accepts.
+fn parse_verse_option(value : &str, locale : &Locale) -> Result<Vec<usize>, String>{
+    if value.trim().eq_ignore_ascii_case("all") {
+        return Ok((1..=locale.days()).collect());
+    }
 
-    let mut verse_number : String = String::new();
-    io::stdin().read_line(&mut verse_number)
-        .expect("Failed to read line, please try again!");
-    let verse_number: usize = verse_number.trim().parse()
-        .expect("Not a valid number, please try again!");
+    Ok(vec![prompt::parse_verse_selector(value, locale)?])
+}
+
+fn run(mut arguments : Vec<String>) -> Result<(), String>{
+    let locale = select_locale(take_option(&mut arguments, "--lang")?);
+
+    //Usage: --gifts <file>, one gift per line, the song gets one day per gift.
+    let gift_list : String;
+    let gifts : Vec<locale::Gift>;
+    let custom_locale : Locale;
+    let locale : &Locale = match take_option(&mut arguments, "--gifts")? {
+        None => locale,
+        Some(path) => {
+            gift_list = fs::read_to_string(&path)
+                .map_err(|error| format!("Failed to read gift list '{}': {}", path, error))?;
+            gifts = locale::parse_gift_list(&gift_list)?;
+            custom_locale = Locale { gifts : &gifts, ..*locale };
+            &custom_locale
+        }
+    };
+
+    let verse_option = take_option(&mut arguments, "--verse")?;
+    let midi_path = take_option(&mut arguments, "--midi")?;
+    let lrc_path = take_option(&mut arguments, "--lrc")?;
+    let srt_path = take_option(&mut arguments, "--srt")?;
+    let tempo_bpm = match take_option(&mut arguments, "--tempo")? {
+        None => DEFAULT_TEMPO_BPM,
+        Some(value) => match value.trim().parse() {
+            Ok(tempo_bpm) if tempo_bpm > 0 && tempo_bpm <= MAX_TEMPO_BPM => tempo_bpm,
+            _ => return Err(format!("Invalid tempo {}, please enter beats per minute between 1 \
+                                     and {}!", value, MAX_TEMPO_BPM)),
+        },
+    };
+
+    if !arguments.is_empty() {
+        let mode = arguments.remove(0);
+        return match mode.as_str() {
+            "stats" => stats_mode(locale, arguments.into_iter().next()),
+            "prices" => prices_mode(arguments, verse_option),
+            _ => Err(format!("Unknown mode '{}', available modes: stats, prices", mode)),
+        };
+    }
+
+    let verses = match verse_option {
+        Some(value) => parse_verse_option(&value, locale)?,
+        None => match prompt::prompt_verse(locale) {
+            Some(verse_number) => vec![verse_number],
+            None => return Ok(()),
+        },
+    };
+
+    for (index, verse_number) in verses.iter().enumerate() {
+        if index > 0 {
+            println!();
+        }
+
+        ordinal_numbers_output(locale, *verse_number);
+        verse_output(locale, *verse_number);
+    }
+
+    if let Some(path) = midi_path {
+        midi::write_midi_file(&path, locale.title, &verses, tempo_bpm)?;
+        println!("Melody written to {}", path);
+    }
+
+    if let Some(path) = lrc_path {
+        karaoke::write_lrc_file(&path, locale, &verses, tempo_bpm)?;
+        println!("LRC lyrics written to {}", path);
+    }
 
-    if verse_number > 0 && verse_number <= 12{
-        ordinal_numbers_output(verse_number);
-        verse_output(verse_number);
+    if let Some(path) = srt_path {
+        karaoke::write_srt_file(&path, locale, &verses, tempo_bpm)?;
+        println!("SRT subtitles written to {}", path);
     }
-    else {
-        println!("Invalid verse number, please enter number between 1 and 12!")
+
+    Ok(())
+}
+
+fn main() {
+    if let Err(error) = run(env::args().skip(1).collect()) {
+        eprintln!("{}", error);
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_language_falls_back_to_english(){
+        assert_eq!(select_locale(None).code, "en");
+        assert_eq!(select_locale(Some(String::from("de"))).code, "de");
+        assert_eq!(select_locale(Some(String::from("xx"))).code, "en");
     }
 }
@@ -0,0 +1,79 @@
+use crate::lyrics::{self, Line};
+
+//Melody of the song, as one phrase per lyric line.
+//Note - This is a simplified arrangement in C major, not a note for note transcription.
+//       Like the lyrics, the melody is cumulative, every gift reuses the same phrase each time it
+//          comes back, and the fifth gift has its own, longer "five golden rings" phrase.
+//       Durations are counted in eighth notes.
+
+//Bar of rest between two verses.
+pub const VERSE_GAP_EIGHTHS : u32 = 8;
+
+pub struct Note {
+    //MIDI note number, 60 is middle C.
+    pub pitch : u8,
+    pub eighths : u32,
+}
+
+const fn note(pitch : u8, eighths : u32) -> Note{
+    Note { pitch, eighths }
+}
+
+const G4 : u8 = 67;
+const B4 : u8 = 71;
+const C5 : u8 = 72;
+const D5 : u8 = 74;
+const E5 : u8 = 76;
+const F5 : u8 = 77;
+const G5 : u8 = 79;
+const A5 : u8 = 81;
+const B5 : u8 = 83;
+
+//"On the first day of Christmas, my true love sent to me"
+const INTRO_PHRASE : [Note; 13] = [note(G4, 1), note(G4, 1), note(G4, 2), note(C5, 1),
+    note(C5, 1), note(C5, 2), note(B4, 1), note(C5, 1), note(D5, 1), note(E5, 1), note(F5, 1),
+    note(D5, 1), note(E5, 3)];
+
+//"A partridge in a pear tree"
+const PARTRIDGE_PHRASE : [Note; 8] = [note(F5, 1), note(G5, 1), note(A5, 1), note(F5, 1),
+    note(E5, 1), note(C5, 1), note(D5, 2), note(C5, 4)];
+
+//"Two turtle doves", "Three french hens" and "Four calling birds"
+const SHORT_GIFT_PHRASE : [Note; 4] = [note(G5, 2), note(D5, 1), note(E5, 1), note(C5, 2)];
+
+//"Five golden rings", held out at the top of the melody.
+const GOLDEN_RINGS_PHRASE : [Note; 4] = [note(G5, 2), note(A5, 2), note(B5, 2),
+    note(G5, 6)];
+
+//"Six geese a-laying" and every gift after it.
+const LONG_GIFT_PHRASE : [Note; 5] = [note(G5, 1), note(D5, 1), note(E5, 1), note(F5, 1),
+    note(D5, 2)];
+
+pub fn intro_phrase() -> &'static [Note]{
+    &INTRO_PHRASE
+}
+
+pub fn gift_phrase(day : usize) -> &'static [Note]{
+    match day {
+        1 => &PARTRIDGE_PHRASE,
+        2..=4 => &SHORT_GIFT_PHRASE,
+        5 => &GOLDEN_RINGS_PHRASE,
+        _ => &LONG_GIFT_PHRASE,
+    }
+}
+
+pub fn line_phrase(line : Line) -> &'static [Note]{
+    match line {
+        Line::Intro => intro_phrase(),
+        Line::Gift(day) => gift_phrase(day),
+    }
+}
+
+//Phrases of a verse in the same order as its lines.
+pub fn verse_phrases(verse_number : usize) -> Vec<&'static [Note]>{
+    lyrics::verse_structure(verse_number).into_iter().map(line_phrase).collect()
+}
+
+pub fn phrase_eighths(phrase : &[Note]) -> u32{
+    phrase.iter().map(|note| note.eighths).sum()
+}
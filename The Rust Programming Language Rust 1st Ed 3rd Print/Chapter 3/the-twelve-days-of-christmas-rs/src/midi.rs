@@ -0,0 +1,222 @@
+use std::fs;
+
+use crate::melody;
+
+//Standard MIDI File writer for the melody.
+//Note - The file is a format 0 file, which is a header chunk ("MThd") followed by a single track
+//          chunk ("MTrk").
+//       Every track event starts with the time since the previous event (delta time) in ticks,
+//          written as a variable length quantity: 7 bits per byte, most significant group first,
+//          and the top bit set on every byte except the last one.
+
+pub const TICKS_PER_QUARTER : u16 = 480;
+const TICKS_PER_EIGHTH : u32 = TICKS_PER_QUARTER as u32 / 2;
+
+const NOTE_ON : u8 = 0x90;
+const NOTE_OFF : u8 = 0x80;
+const PROGRAM_CHANGE : u8 = 0xC0;
+const META_EVENT : u8 = 0xFF;
+const META_TRACK_NAME : u8 = 0x03;
+const META_TEMPO : u8 = 0x51;
+const META_END_OF_TRACK : u8 = 0x2F;
+
+const VELOCITY : u8 = 96;
+//General MIDI program 9, the glockenspiel.
+const INSTRUMENT : u8 = 9;
+
+pub fn write_variable_length(value : u32, bytes : &mut Vec<u8>){
+    //Only 28 bits fit in the four bytes a variable length quantity is allowed to take.
+    let value = value & 0x0FFF_FFFF;
+    let mut groups = vec![(value & 0x7F) as u8];
+    let mut rest = value >> 7;
+
+    while rest > 0 {
+        groups.push((rest & 0x7F) as u8 | 0x80);
+        rest >>= 7;
+    }
+
+    bytes.extend(groups.iter().rev());
+}
+
+struct Track {
+    bytes : Vec<u8>,
+    //Ticks waited since the last written event, added to the delta time of the next one.
+    pending_ticks : u32,
+}
+
+impl Track {
+    fn new() -> Track{
+        Track { bytes : Vec::new(), pending_ticks : 0 }
+    }
+
+    fn event(&mut self, data : &[u8]){
+        write_variable_length(self.pending_ticks, &mut self.bytes);
+        self.bytes.extend_from_slice(data);
+        self.pending_ticks = 0;
+    }
+
+    fn meta_event(&mut self, kind : u8, data : &[u8]){
+        let mut event = vec![META_EVENT, kind];
+        write_variable_length(data.len() as u32, &mut event);
+        event.extend_from_slice(data);
+        self.event(&event);
+    }
+
+    fn note(&mut self, pitch : u8, ticks : u32){
+        self.event(&[NOTE_ON, pitch, VELOCITY]);
+        self.pending_ticks = ticks;
+        self.event(&[NOTE_OFF, pitch, 0]);
+    }
+
+    fn rest(&mut self, ticks : u32){
+        self.pending_ticks += ticks;
+    }
+}
+
+fn chunk(kind : &[u8; 4], data : &[u8]) -> Vec<u8>{
+    let mut bytes = kind.to_vec();
+    bytes.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    bytes.extend_from_slice(data);
+    bytes
+}
+
+//Whole file for the given verses, played one after the other with a bar of rest in between.
+pub fn melody_file(title : &str, verses : &[usize], tempo_bpm : u32) -> Vec<u8>{
+    let mut track = Track::new();
+    let microseconds_per_quarter = 60_000_000 / tempo_bpm.max(1);
+
+    track.meta_event(META_TRACK_NAME, title.as_bytes());
+    track.meta_event(META_TEMPO, &microseconds_per_quarter.to_be_bytes()[1..]);
+    track.event(&[PROGRAM_CHANGE, INSTRUMENT]);
+
+    for (index, verse_number) in verses.iter().enumerate() {
+        if index > 0 {
+            track.rest(melody::VERSE_GAP_EIGHTHS * TICKS_PER_EIGHTH);
+        }
+
+        for phrase in melody::verse_phrases(*verse_number) {
+            for note in phrase {
+                track.note(note.pitch, note.eighths * TICKS_PER_EIGHTH);
+            }
+        }
+    }
+
+    track.meta_event(META_END_OF_TRACK, &[]);
+
+    //Format 0, one track, then the time division in ticks per quarter note.
+    let mut header = Vec::new();
+    header.extend_from_slice(&0u16.to_be_bytes());
+    header.extend_from_slice(&1u16.to_be_bytes());
+    header.extend_from_slice(&TICKS_PER_QUARTER.to_be_bytes());
+
+    let mut bytes = chunk(b"MThd", &header);
+    bytes.extend(chunk(b"MTrk", &track.bytes));
+    bytes
+}
+
+pub fn write_midi_file(path : &str, title : &str, verses : &[usize], tempo_bpm : u32)
+                       -> Result<(), String>{
+    fs::write(path, melody_file(title, verses, tempo_bpm))
+        .map_err(|error| format!("Failed to write MIDI file '{}': {}", path, error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variable_length(value : u32) -> Vec<u8>{
+        let mut bytes = Vec::new();
+        write_variable_length(value, &mut bytes);
+        bytes
+    }
+
+    #[test]
+    fn variable_length_quantities(){
+        assert_eq!(variable_length(0), [0x00]);
+        assert_eq!(variable_length(0x40), [0x40]);
+        assert_eq!(variable_length(0x7F), [0x7F]);
+        assert_eq!(variable_length(0x80), [0x81, 0x00]);
+        assert_eq!(variable_length(0x2000), [0xC0, 0x00]);
+        assert_eq!(variable_length(0x3FFF), [0xFF, 0x7F]);
+        assert_eq!(variable_length(0x4000), [0x81, 0x80, 0x00]);
+        assert_eq!(variable_length(0x0FFF_FFFF), [0xFF, 0xFF, 0xFF, 0x7F]);
+    }
+
+    #[test]
+    fn header_chunk(){
+        let bytes = melody_file("Test", &[1], 120);
+
+        assert_eq!(&bytes[..14], &[b'M', b'T', b'h', b'd', 0, 0, 0, 6, 0, 0, 0, 1, 0x01, 0xE0]);
+        assert_eq!(&bytes[14..18], b"MTrk");
+    }
+
+    #[test]
+    fn track_chunk_length_and_end(){
+        let bytes = melody_file("Test", &[5, 12], 120);
+        let length = u32::from_be_bytes([bytes[18], bytes[19], bytes[20], bytes[21]]) as usize;
+
+        assert_eq!(bytes.len(), 22 + length);
+        assert_eq!(&bytes[bytes.len() - 4..], &[0x00, 0xFF, 0x2F, 0x00]);
+    }
+
+    #[test]
+    fn track_starts_with_name_tempo_and_program(){
+        let bytes = melody_file("Test", &[1], 120);
+
+        //Delta 0, track name "Test"
+        assert_eq!(&bytes[22..30], &[0x00, 0xFF, 0x03, 0x04, b'T', b'e', b's', b't']);
+        //Delta 0, tempo of 500000 microseconds per quarter note (120 bpm)
+        assert_eq!(&bytes[30..37], &[0x00, 0xFF, 0x51, 0x03, 0x07, 0xA1, 0x20]);
+        //Delta 0, program change
+        assert_eq!(&bytes[37..40], &[0x00, 0xC0, INSTRUMENT]);
+        //Delta 0, first note of the intro, G4
+        assert_eq!(&bytes[40..44], &[0x00, 0x90, 67, VELOCITY]);
+        //Held for an eighth note (240 ticks), then released
+        assert_eq!(&bytes[44..49], &[0x81, 0x70, 0x80, 67, 0x00]);
+    }
+
+    fn read_variable_length(bytes : &[u8], position : &mut usize) -> u32{
+        let mut value = 0;
+
+        loop {
+            let byte = bytes[*position];
+            *position += 1;
+            value = (value << 7) | (byte & 0x7F) as u32;
+
+            if byte & 0x80 == 0 {
+                return value;
+            }
+        }
+    }
+
+    #[test]
+    fn one_note_on_and_off_per_note(){
+        let bytes = melody_file("", &[12], 120);
+        let notes : usize = melody::verse_phrases(12).iter().map(|phrase| phrase.len()).sum();
+        let (mut note_ons, mut note_offs) = (0, 0);
+        let mut position = 22;
+
+        //Walks the track event by event, so every length has to line up for this to finish.
+        while position < bytes.len() {
+            read_variable_length(&bytes, &mut position);
+            let status = bytes[position];
+            position += 1;
+
+            match status {
+                NOTE_ON => { note_ons += 1; position += 2; }
+                NOTE_OFF => { note_offs += 1; position += 2; }
+                PROGRAM_CHANGE => position += 1,
+                META_EVENT => {
+                    position += 1;
+                    let length = read_variable_length(&bytes, &mut position);
+                    position += length as usize;
+                }
+                _ => panic!("Unexpected status byte {:#04X} at {}", status, position - 1),
+            }
+        }
+
+        assert_eq!(position, bytes.len());
+        assert_eq!(note_ons, notes);
+        assert_eq!(note_offs, notes);
+    }
+}
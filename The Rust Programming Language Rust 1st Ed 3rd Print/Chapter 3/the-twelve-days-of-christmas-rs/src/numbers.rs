@@ -0,0 +1,110 @@
+//English number words, so songs can have any number of days.
+//Note - The ordinal is the cardinal with its last word changed, e.g.
+//          "twenty-one" -> "twenty-first" and "one hundred twelve" -> "one hundred twelfth".
+
+const ONES : [&str; 20] = ["zero", "one", "two", "three", "four", "five", "six", "seven", "eight",
+    "nine", "ten", "eleven", "twelve", "thirteen", "fourteen", "fifteen", "sixteen", "seventeen",
+    "eighteen", "nineteen"];
+
+const TENS : [&str; 10] = ["", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy",
+    "eighty", "ninety"];
+
+const SCALES : [(usize, &str); 3] = [(1_000_000_000, "billion"), (1_000_000, "million"),
+    (1_000, "thousand")];
+
+fn below_thousand(number : usize) -> String{
+    let mut words = Vec::new();
+
+    if number >= 100 {
+        words.push(format!("{} hundred", ONES[number / 100]));
+    }
+
+    match number % 100 {
+        0 => {}
+        rest @ 1..=19 => words.push(ONES[rest].to_string()),
+        rest if rest % 10 == 0 => words.push(TENS[rest / 10].to_string()),
+        rest => words.push(format!("{}-{}", TENS[rest / 10], ONES[rest % 10])),
+    }
+
+    words.join(" ")
+}
+
+pub fn cardinal(number : usize) -> String{
+    if number == 0 {
+        return ONES[0].to_string();
+    }
+
+    let mut words = Vec::new();
+    let mut rest = number;
+
+    for (scale, scale_word) in SCALES.iter() {
+        if rest >= *scale {
+            words.push(format!("{} {}", cardinal(rest / scale), scale_word));
+            rest %= scale;
+        }
+    }
+
+    if rest > 0 {
+        words.push(below_thousand(rest));
+    }
+
+    words.join(" ")
+}
+
+fn ordinal_word(word : &str) -> String{
+    match word {
+        "one" => String::from("first"),
+        "two" => String::from("second"),
+        "three" => String::from("third"),
+        "five" => String::from("fifth"),
+        "eight" => String::from("eighth"),
+        "nine" => String::from("ninth"),
+        "twelve" => String::from("twelfth"),
+        _ => match word.strip_suffix('y') {
+            Some(stem) => format!("{}ieth", stem),
+            None => format!("{}th", word),
+        },
+    }
+}
+
+pub fn ordinal(number : usize) -> String{
+    let cardinal = cardinal(number);
+    let split_at = cardinal.rfind([' ', '-']).map_or(0, |index| index + 1);
+    let (head, last_word) = cardinal.split_at(split_at);
+
+    format!("{}{}", head, ordinal_word(last_word))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cardinals(){
+        assert_eq!(cardinal(0), "zero");
+        assert_eq!(cardinal(7), "seven");
+        assert_eq!(cardinal(13), "thirteen");
+        assert_eq!(cardinal(40), "forty");
+        assert_eq!(cardinal(21), "twenty-one");
+        assert_eq!(cardinal(112), "one hundred twelve");
+        assert_eq!(cardinal(1_000), "one thousand");
+        assert_eq!(cardinal(2_000_305), "two million three hundred five");
+        assert_eq!(cardinal(1_234_567_890), "one billion two hundred thirty-four million five \
+            hundred sixty-seven thousand eight hundred ninety");
+    }
+
+    #[test]
+    fn ordinals(){
+        assert_eq!(ordinal(1), "first");
+        assert_eq!(ordinal(3), "third");
+        assert_eq!(ordinal(12), "twelfth");
+        assert_eq!(ordinal(13), "thirteenth");
+        assert_eq!(ordinal(20), "twentieth");
+        assert_eq!(ordinal(21), "twenty-first");
+        assert_eq!(ordinal(40), "fortieth");
+        assert_eq!(ordinal(99), "ninety-ninth");
+        assert_eq!(ordinal(100), "one hundredth");
+        assert_eq!(ordinal(112), "one hundred twelfth");
+        assert_eq!(ordinal(1_000_002), "one million second");
+    }
+}
@@ -0,0 +1,215 @@
+use std::fs;
+
+use crate::stats;
+
+//Christmas price index calculator, reading item prices from a local CSV file.
+//Note - The file is expected to look like this, one row per day of the song in order, and one
+//          column of unit prices per year:
+//              item,2023,2024
+//              partridge in a pear tree,250.00,275.00
+//              turtle dove,50.00,55.00
+//              ...
+//       Empty lines and lines starting with '#' are ignored.
+pub struct PriceTable {
+    pub years : Vec<String>,
+    pub items : Vec<String>,
+    //unit_prices[day - 1][year column]
+    unit_prices : Vec<Vec<f64>>,
+}
+
+impl PriceTable {
+    pub fn load(path : &str) -> Result<PriceTable, String>{
+        let contents = fs::read_to_string(path)
+            .map_err(|error| format!("Failed to read price file '{}': {}", path, error))?;
+
+        PriceTable::parse(&contents)
+    }
+
+    pub fn parse(contents : &str) -> Result<PriceTable, String>{
+        let mut rows = contents.lines().enumerate()
+            .map(|(index, line)| (index + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+
+        let (_, header) = rows.next().ok_or("Price file is empty")?;
+        let mut header = header.split(',').map(str::trim);
+
+        if header.next() != Some("item") {
+            return Err(String::from("Price file header must start with the 'item' column"));
+        }
+
+        let years : Vec<String> = header.map(String::from).collect();
+        if years.is_empty() {
+            return Err(String::from("Price file header has no year columns"));
+        }
+
+        let mut items = Vec::new();
+        let mut unit_prices = Vec::new();
+
+        for (line_number, line) in rows {
+            let mut fields = line.split(',').map(str::trim);
+            let item = fields.next().unwrap_or_default().to_string();
+
+            let prices : Vec<f64> = fields
+                .map(|field| field.parse::<f64>().ok()
+                    .filter(|price| price.is_finite() && *price >= 0.0)
+                    .ok_or(format!("Line {}: '{}' is not a valid price", line_number, field)))
+                .collect::<Result<_, _>>()?;
+
+            if prices.len() != years.len() {
+                return Err(format!("Line {}: expected {} price(s) but found {}",
+                                   line_number, years.len(), prices.len()));
+            }
+
+            items.push(item);
+            unit_prices.push(prices);
+        }
+
+        if items.is_empty() {
+            return Err(String::from("Price file has no items"));
+        }
+
+        Ok(PriceTable { years, items, unit_prices })
+    }
+
+    pub fn days(&self) -> usize{
+        self.items.len()
+    }
+
+    pub fn year_column(&self, year : &str) -> Result<usize, String>{
+        self.years.iter().position(|column| column == year)
+            .ok_or(format!("Year {} is not in the price file (available: {})",
+                           year, self.years.join(", ")))
+    }
+
+    //Cost of the gifts sung in one verse, e.g. verse 3 is 1 partridge, 2 doves and 3 hens.
+    pub fn verse_cost(&self, column : usize, verse_number : usize) -> f64{
+        (1..=verse_number.min(self.days()))
+            .map(|day| day as f64 * self.unit_prices[day - 1][column])
+            .sum()
+    }
+
+    //Cost of the whole song sung once, which is every gift line once (the final verse).
+    pub fn song_cost(&self, column : usize) -> f64{
+        self.verse_cost(column, self.days())
+    }
+
+    //Cost of every gift actually sent, counting the repeats from each day (364 gifts for 12 days).
+    pub fn cumulative_cost(&self, column : usize) -> f64{
+        (1..=self.days())
+            .map(|day| stats::gift_total(day, self.days()) as f64
+                * self.unit_prices[day - 1][column])
+            .sum()
+    }
+}
+
+//The verse line is only shown for --verse, by default the whole song already is the last verse.
+pub fn prices_output(table : &PriceTable, year : &str, verse_number : Option<usize>)
+                     -> Result<(), String>{
+    let column = table.year_column(year)?;
+
+    println!("Christmas price index for {}", year);
+    if let Some(verse_number) = verse_number {
+        println!("Verse {}: {:.2}", verse_number, table.verse_cost(column, verse_number));
+    }
+    println!("Whole song: {:.2}", table.song_cost(column));
+    println!("Cumulative gifts ({} items): {:.2}", stats::tetrahedral_number(table.days()),
+             table.cumulative_cost(column));
+
+    Ok(())
+}
+
+pub fn comparison_output(table : &PriceTable, from_year : &str, to_year : &str,
+                         verse_number : Option<usize>) -> Result<(), String>{
+    let from_column = table.year_column(from_year)?;
+    let to_column = table.year_column(to_year)?;
+
+    println!("Christmas price index, {} compared to {}", to_year, from_year);
+    println!("{:<20} {:>14} {:>14} {:>14} {:>9}", "", from_year, to_year, "Change", "Percent");
+
+    let mut rows = Vec::new();
+    if let Some(verse_number) = verse_number {
+        rows.push((format!("Verse {}", verse_number), table.verse_cost(from_column, verse_number),
+                   table.verse_cost(to_column, verse_number)));
+    }
+    rows.push((String::from("Whole song"), table.song_cost(from_column),
+               table.song_cost(to_column)));
+    rows.push((String::from("Cumulative gifts"), table.cumulative_cost(from_column),
+               table.cumulative_cost(to_column)));
+
+    for (label, from_cost, to_cost) in rows.iter() {
+        let percent = if *from_cost == 0.0 {
+            String::from("n/a")
+        } else {
+            format!("{:+.2}%", (to_cost - from_cost) / from_cost * 100.0)
+        };
+
+        println!("{:<20} {:>14.2} {:>14.2} {:>+14.2} {:>9}", label, from_cost, to_cost,
+                 to_cost - from_cost, percent);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE : &str = "# Prices in dollars\n\
+                           item,2023,2024\n\
+                           \n\
+                           partridge in a pear tree,250.00,275.00\n\
+                           # The doves got cheaper\n\
+                           turtle dove,50.00,45.00\n\
+                           french hen,10.00,12.50\n";
+
+    #[test]
+    fn parses_prices_and_skips_comments(){
+        let table = PriceTable::parse(SAMPLE).unwrap();
+
+        assert_eq!(table.years, ["2023", "2024"]);
+        assert_eq!(table.items, ["partridge in a pear tree", "turtle dove", "french hen"]);
+        assert_eq!(table.year_column("2024"), Ok(1));
+        assert!(table.year_column("1999").is_err());
+
+        assert_eq!(table.verse_cost(0, 1), 250.0);
+        assert_eq!(table.verse_cost(0, 2), 350.0);
+        assert_eq!(table.song_cost(1), 275.0 + 90.0 + 37.5);
+        //3 partridges, 2 x 2 doves and 3 hens.
+        assert_eq!(table.cumulative_cost(0), 3.0 * 250.0 + 4.0 * 50.0 + 3.0 * 10.0);
+    }
+
+    #[test]
+    fn rejects_bad_files(){
+        let error = |contents : &str| PriceTable::parse(contents).err().unwrap_or_default();
+
+        assert_eq!(error(""), "Price file is empty");
+        assert_eq!(error("name,2023\npartridge,1.00"),
+                   "Price file header must start with the 'item' column");
+        assert_eq!(error("item\npartridge"), "Price file header has no year columns");
+        assert_eq!(error("item,2023,2024\npartridge,1.00"),
+                   "Line 2: expected 2 price(s) but found 1");
+        assert_eq!(error("item,2023\npartridge,1.00,2.00"),
+                   "Line 2: expected 1 price(s) but found 2");
+        assert_eq!(error("item,2023\n# comment\npartridge,-1.00"),
+                   "Line 3: '-1.00' is not a valid price");
+        assert_eq!(error("item,2023\npartridge,free"), "Line 2: 'free' is not a valid price");
+        assert_eq!(error("item,2023\npartridge,inf"), "Line 2: 'inf' is not a valid price");
+        assert_eq!(error("item,2023\npartridge,infinity"),
+                   "Line 2: 'infinity' is not a valid price");
+        assert_eq!(error("item,2023\npartridge,NaN"), "Line 2: 'NaN' is not a valid price");
+        assert_eq!(error("item,2023\n# nothing yet"), "Price file has no items");
+    }
+
+    #[test]
+    fn cumulative_cost_counts_all_364_gifts(){
+        let mut contents = String::from("item,2024\n");
+        for day in 1..=12 {
+            contents.push_str(&format!("gift {},1.00\n", day));
+        }
+        let table = PriceTable::parse(&contents).unwrap();
+
+        assert_eq!(table.song_cost(0), 78.0);
+        assert_eq!(table.cumulative_cost(0), 364.0);
+        assert_eq!(table.cumulative_cost(0), stats::tetrahedral_number(12) as f64);
+    }
+}
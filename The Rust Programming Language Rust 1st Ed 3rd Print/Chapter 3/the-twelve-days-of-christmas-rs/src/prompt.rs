@@ -0,0 +1,139 @@
+use std::io;
+
+use crate::locale::{self, Locale};
+
+//Interactive verse selection.
+//Note - Unlike .expect(), which crashes the program, a bad answer here just asks again.
+//       The verse can be given as a number ("5"), a numeral with its suffix ("5th"), or an ordinal
+//          word, either in English ("fifth") or in the language of the song ("cinquième").
+
+fn ordinal_suffix(number : usize) -> &'static str{
+    match (number % 10, number % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    }
+}
+
+pub fn parse_verse_selector(input : &str, locale : &Locale) -> Result<usize, String>{
+    let days = locale.days();
+    let input = input.trim().to_lowercase();
+
+    if input.is_empty() {
+        return Err(String::from("Please enter a verse, for example 5, 5th or fifth."));
+    }
+
+    let digits_end = input.find(|character : char| !character.is_ascii_digit())
+        .unwrap_or(input.len());
+
+    let verse_number = if digits_end > 0 {
+        let (digits, suffix) = input.split_at(digits_end);
+        let number : usize = digits.parse()
+            .map_err(|_| format!("{} is too large to be a verse number.", digits))?;
+
+        if !suffix.is_empty() && suffix != ordinal_suffix(number) {
+            return Err(format!("'{}' is not a valid number, did you mean {}{}?", input, number,
+                               ordinal_suffix(number)));
+        }
+
+        number
+    } else {
+        (1..=days)
+            .find(|verse_number| [locale, &locale::ENGLISH].iter()
+                .any(|locale| locale.ordinal(*verse_number).as_deref() == Some(input.as_str())))
+            .ok_or(format!("'{}' is not a verse number or ordinal, please try again!", input))?
+    };
+
+    if verse_number == 0 || verse_number > days {
+        return Err(format!("Invalid verse number, please enter number between 1 and {}!", days));
+    }
+
+    Ok(verse_number)
+}
+
+//Keeps asking until a valid verse is entered, None when the input ends (EOF) or cannot be read.
+pub fn prompt_verse(locale : &Locale) -> Option<usize>{
+    let days = locale.days();
+
+    println!("Welcome to The Twelve Days of Christmas Program!");
+    println!("Please enter the verse you want to choose for the lyrics [Verse 1 - {}].", days);
+
+    loop {
+        let mut verse_number : String = String::new();
+
+        match io::stdin().read_line(&mut verse_number) {
+            Ok(0) => {
+                println!("No verse chosen, goodbye!");
+                return None;
+            }
+            Ok(_) => {}
+            Err(error) => {
+                println!("Failed to read line: {}", error);
+                return None;
+            }
+        }
+
+        match parse_verse_selector(&verse_number, locale) {
+            Ok(verse_number) => return Some(verse_number),
+            Err(error) => println!("{}", error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn english(input : &str) -> Result<usize, String>{
+        parse_verse_selector(input, &locale::ENGLISH)
+    }
+
+    #[test]
+    fn numbers_and_numerals(){
+        assert_eq!(english("5"), Ok(5));
+        assert_eq!(english(" 12\n"), Ok(12));
+        assert_eq!(english("1st"), Ok(1));
+        assert_eq!(english("2nd"), Ok(2));
+        assert_eq!(english("3RD"), Ok(3));
+        assert_eq!(english("5th"), Ok(5));
+        assert_eq!(english("11th"), Ok(11));
+        assert_eq!(english("12th"), Ok(12));
+
+        assert_eq!(english("5nd"),
+                   Err(String::from("'5nd' is not a valid number, did you mean 5th?")));
+        assert_eq!(english("11st"),
+                   Err(String::from("'11st' is not a valid number, did you mean 11th?")));
+    }
+
+    #[test]
+    fn ordinal_words(){
+        assert_eq!(english("fifth"), Ok(5));
+        assert_eq!(english("Twelfth"), Ok(12));
+        assert_eq!(parse_verse_selector("cinquième", &locale::FRENCH), Ok(5));
+        assert_eq!(parse_verse_selector("premier", &locale::FRENCH), Ok(1));
+        //English words still work with another language.
+        assert_eq!(parse_verse_selector("fifth", &locale::FRENCH), Ok(5));
+        assert!(english("cinquième").is_err());
+    }
+
+    #[test]
+    fn bad_answers(){
+        let out_of_range = Err(String::from(
+            "Invalid verse number, please enter number between 1 and 12!"));
+
+        assert_eq!(english(""), Err(String::from(
+            "Please enter a verse, for example 5, 5th or fifth.")));
+        assert_eq!(english("   \n"), english(""));
+        assert_eq!(english("0"), out_of_range);
+        assert_eq!(english("13"), out_of_range);
+        assert_eq!(english("13th"), out_of_range);
+        assert_eq!(english("thirteenth"), Err(String::from(
+            "'thirteenth' is not a verse number or ordinal, please try again!")));
+        assert!(english("five").is_err());
+        assert!(english("-1").is_err());
+        assert!(english("abc").is_err());
+        assert!(english("99999999999999999999999").is_err());
+    }
+}
@@ -0,0 +1,103 @@
+//Gift counting for The Twelve Days of Christmas.
+//Note - On day d the true love sends every gift from the first one up to the d-th one again.
+//       Hence, the gift introduced on day k arrives k at a time on each day from day k onwards,
+//          which is (days - k + 1) deliveries.
+//       The number of gifts received on day d is the triangular number d(d + 1) / 2, and the sum
+//          of the first n triangular numbers is the tetrahedral number n(n + 1)(n + 2) / 6.
+//          For the original twelve days, that is 12 x 13 x 14 / 6 = 364 gifts.
+
+//Largest song length supported by the stats mode, the tetrahedral number still fits in a u64 well
+//past this point.
+pub const MAX_STATS_DAYS : usize = 100_000;
+
+//How many of the gift introduced on day `gift` are received across a song of `days` days.
+pub fn gift_total(gift : usize, days : usize) -> u64{
+    if gift == 0 || gift > days {
+        return 0;
+    }
+
+    (gift as u64) * ((days - gift + 1) as u64)
+}
+
+//How many gifts arrive on day `day` (the triangular number of `day`).
+pub fn gifts_on_day(day : usize) -> u64{
+    let day = day as u64;
+    day * (day + 1) / 2
+}
+
+//Closed form for the grand total of a song of `days` days.
+pub fn tetrahedral_number(days : usize) -> u64{
+    let days = days as u64;
+    days * (days + 1) * (days + 2) / 6
+}
+
+//Prints the per gift and per day totals, `gift_name` provides the label for each gift line.
+pub fn stats_output(days : usize, gift_name : impl Fn(usize) -> String){
+    println!("Gift totals for a song of {} day(s)", days);
+    println!();
+    println!("{:>4}  {:<30} {:>8} {:>8} {:>10}", "Day", "Gift", "Each", "Times", "Total");
+
+    for gift in 1..=days {
+        println!("{:>4}  {:<30} {:>8} {:>8} {:>10}", gift, gift_name(gift), gift,
+                 days - gift + 1, gift_total(gift, days));
+    }
+
+    println!();
+    println!("{:>4}  {:>10} {:>12}", "Day", "Received", "Running");
+
+    let mut running_total : u64 = 0;
+    for day in 1..=days {
+        running_total += gifts_on_day(day);
+        println!("{:>4}  {:>10} {:>12}", day, gifts_on_day(day), running_total);
+    }
+
+    println!();
+    println!("Grand total: {} gifts (tetrahedral number {} x {} x {} / 6)",
+             tetrahedral_number(days), days, days + 1, days + 2);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    //Counts every single gift one by one, used to double check the closed form.
+    fn brute_force_total(days : usize) -> u64{
+        let mut total : u64 = 0;
+
+        for day in 1..=days {
+            for gift in 1..=day {
+                total += gift as u64;
+            }
+        }
+
+        total
+    }
+
+    #[test]
+    fn twelve_days_total_is_364(){
+        assert_eq!(tetrahedral_number(12), 364);
+        assert_eq!(brute_force_total(12), 364);
+    }
+
+    #[test]
+    fn closed_form_matches_brute_force(){
+        for days in 0..=200 {
+            assert_eq!(tetrahedral_number(days), brute_force_total(days), "days = {}", days);
+        }
+    }
+
+    #[test]
+    fn per_gift_totals_add_up(){
+        assert_eq!(gift_total(1, 12), 12);
+        assert_eq!(gift_total(2, 12), 22);
+        assert_eq!(gift_total(12, 12), 12);
+        assert_eq!(gift_total(13, 12), 0);
+
+        for days in 1..=50 {
+            let by_gift : u64 = (1..=days).map(|gift| gift_total(gift, days)).sum();
+            let by_day : u64 = (1..=days).map(gifts_on_day).sum();
+            assert_eq!(by_gift, tetrahedral_number(days));
+            assert_eq!(by_day, tetrahedral_number(days));
+        }
+    }
+}